@@ -0,0 +1,63 @@
+//! Generates the solution registry, which compiles every solution in `src/bin` into the library.
+//! It is only included with the `registry` feature, see `src/template/registry.rs`.
+use std::{env, fs, path::Path};
+
+/// Solution binaries are named `DD` or `YYYY-DD`.
+fn is_solution(name: &str) -> bool {
+    let day = match name.split_once('-') {
+        Some((year, day)) if year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()) => day,
+        Some(_) => return false,
+        None => name,
+    };
+    day.len() == 2 && matches!(day.parse::<u8>(), Ok(1..=25))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let name = path.file_stem()?.to_str()?.to_string();
+                    is_solution(&name).then_some(name)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for name in &names {
+        let module = format!("day_{}", name.replace('-', "_"));
+        let path = bin_dir.join(format!("{name}.rs"));
+        let day: u8 = name[name.len() - 2..].parse().unwrap();
+
+        // lints of solutions are reported when building their binaries, not the library.
+        modules.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(warnings, clippy::all, clippy::pedantic)]\n#[path = {:?}]\nmod {module};\n",
+            path.display().to_string()
+        ));
+        entries.push_str(&format!(
+            "    Solution {{\n        name: {name:?},\n        day: crate::day!({day}),\n        parts: [\n            |input| crate::template::runner::run_part({module}::part_one, input, crate::day!({day}), 1),\n            |input| crate::template::runner::run_part({module}::part_two, input, crate::day!({day}), 2),\n        ],\n    }},\n"
+        ));
+    }
+
+    let out = format!(
+        "{modules}\n/// Every solution in `src/bin`, sorted by binary name.\n#[cfg(not(test))]\npub static SOLUTIONS: &[Solution] = &[\n{entries}];\n\n#[cfg(test)]\npub static SOLUTIONS: &[Solution] = &[];\n"
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), out).unwrap();
+}
@@ -1,3 +1,7 @@
+// solutions included by the registry refer to the library by its name.
+#[cfg(feature = "registry")]
+extern crate self as advent_of_code;
+
 mod day;
 pub mod template;
 
@@ -1,17 +1,33 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, archive, doctor, download, examples, leaderboard, read, scaffold, session, solve, stars,
+    status,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{path::PathBuf, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{commands::session::SessionCommand, test_report::Report},
+        Day,
+    };
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
+            part: Option<u8>,
+            refresh: bool,
+        },
+        Examples {
+            day: Day,
+            list: bool,
+            part_one: Option<usize>,
+            part_two: Option<usize>,
+            force: bool,
         },
         Scaffold {
             day: Day,
@@ -20,11 +36,28 @@ mod args {
             day: Day,
             release: bool,
             time: bool,
+            profile: bool,
             submit: Option<u8>,
         },
         All {
             release: bool,
             time: bool,
+            export: Option<PathBuf>,
+            jobs: usize,
+            fail_fast: bool,
+            reports: Vec<Report>,
+        },
+        Status {
+            run: bool,
+        },
+        Archive,
+        Doctor,
+        Stars,
+        Session(SessionCommand),
+        Leaderboard {
+            id: u64,
+            refresh: bool,
+            offline: bool,
         },
     }
 
@@ -35,12 +68,26 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                export: args.opt_value_from_str("--export")?,
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                fail_fast: args.contains("--fail-fast"),
+                reports: args.values_from_str("--report")?,
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?,
+                refresh: args.contains("--refresh"),
+            },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+                list: args.contains("--list"),
+                part_one: args.opt_value_from_str("--part-one")?,
+                part_two: args.opt_value_from_str("--part-two")?,
+                force: args.contains("--force"),
             },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
@@ -50,7 +97,20 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                profile: args.contains("--profile"),
+            },
+            Some("status") => AppArguments::Status {
+                run: args.contains("--run"),
+            },
+            Some("archive") => AppArguments::Archive,
+            Some("doctor") => AppArguments::Doctor,
+            Some("stars") => AppArguments::Stars,
+            Some("leaderboard") => AppArguments::Leaderboard {
+                id: args.free_from_str()?,
+                refresh: args.contains("--refresh"),
+                offline: args.contains("--offline"),
             },
+            Some("session") => AppArguments::Session(parse_session(&mut args)?),
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -61,6 +121,9 @@ mod args {
             }
         };
 
+        // `--year` is available for every command and read by `config::get()`.
+        let _: Option<u16> = args.opt_value_from_str("--year")?;
+
         let remaining = args.finish();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
@@ -68,6 +131,29 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_session(
+        args: &mut pico_args::Arguments,
+    ) -> Result<SessionCommand, Box<dyn std::error::Error>> {
+        let command = match args.subcommand()?.as_deref() {
+            Some("set") => SessionCommand::Set {
+                profile: args.opt_value_from_str("--profile")?,
+                cookie: args.free_from_str()?,
+            },
+            Some("show") | None => SessionCommand::Show,
+            Some("validate") => SessionCommand::Validate,
+            Some("list") => SessionCommand::List,
+            Some("switch") => SessionCommand::Switch {
+                profile: args.free_from_str()?,
+            },
+            Some(x) => {
+                eprintln!("Unknown session command: {x}. Use set, show, validate, list or switch.");
+                process::exit(1);
+            }
+        };
+
+        Ok(command)
+    }
 }
 
 fn main() {
@@ -77,16 +163,41 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                release,
+                time,
+                export,
+                jobs,
+                fail_fast,
+                reports,
+            } => all::handle(release, time, export, jobs, fail_fast, &reports),
+            AppArguments::Download { day, force } => download::handle(day, force),
+            AppArguments::Read { day, part, refresh } => read::handle(day, part, refresh),
+            AppArguments::Examples {
+                day,
+                list,
+                part_one,
+                part_two,
+                force,
+            } => examples::handle(day, list, part_one, part_two, force),
             AppArguments::Scaffold { day } => scaffold::handle(day),
             AppArguments::Solve {
                 day,
                 release,
                 time,
+                profile,
                 submit,
-            } => solve::handle(day, release, time, submit),
+            } => solve::handle(day, release, time, profile, submit),
+            AppArguments::Status { run } => status::handle(run),
+            AppArguments::Archive => archive::handle(),
+            AppArguments::Doctor => doctor::handle(),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Session(command) => session::handle(command),
+            AppArguments::Leaderboard {
+                id,
+                refresh,
+                offline,
+            } => leaderboard::handle(id, refresh, offline),
         },
     };
 }
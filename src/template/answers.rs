@@ -0,0 +1,201 @@
+//! A small local store of the last answer and timing recorded for each part of a day.
+//! Records live in `data/answers/DD.toml` and are written by the runner.
+use std::{fs, io, path::PathBuf, time::Duration};
+
+use crate::template::config;
+use crate::template::toml_lite::{Document, Value};
+use crate::Day;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartRecord {
+    /// The last answer returned by the solution, [`None`] if it returned [`None`].
+    pub answer: Option<String>,
+    /// The last benched execution time and its sample count.
+    pub timing: Option<(Duration, u128)>,
+    /// Time spent in `parse` spans during the last benched run.
+    pub parse: Option<Duration>,
+    /// Peak heap usage in bytes during the last benched run.
+    pub memory: Option<u64>,
+    /// Whether the website accepted a submission for this part.
+    pub solved: bool,
+    /// The answer the website confirmed as correct, used to detect regressions.
+    pub accepted: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DayRecord {
+    pub part_1: PartRecord,
+    pub part_2: PartRecord,
+}
+
+impl DayRecord {
+    pub fn part(&self, part: u8) -> &PartRecord {
+        if part == 1 {
+            &self.part_1
+        } else {
+            &self.part_2
+        }
+    }
+
+    pub fn part_mut(&mut self, part: u8) -> &mut PartRecord {
+        if part == 1 {
+            &mut self.part_1
+        } else {
+            &mut self.part_2
+        }
+    }
+
+    fn from_document(doc: &Document) -> Self {
+        let part = |table: &str| PartRecord {
+            answer: doc
+                .get(table, "answer")
+                .and_then(Value::as_str)
+                .map(String::from),
+            timing: doc
+                .get(table, "nanos")
+                .and_then(Value::as_integer)
+                .zip(doc.get(table, "samples").and_then(Value::as_integer))
+                .map(|(nanos, samples)| {
+                    (
+                        Duration::from_nanos(nanos.try_into().unwrap_or_default()),
+                        samples.try_into().unwrap_or_default(),
+                    )
+                }),
+            parse: doc
+                .get(table, "parse_nanos")
+                .and_then(Value::as_integer)
+                .map(|nanos| Duration::from_nanos(nanos.try_into().unwrap_or_default())),
+            memory: doc
+                .get(table, "memory")
+                .and_then(Value::as_integer)
+                .and_then(|bytes| bytes.try_into().ok()),
+            solved: doc
+                .get(table, "solved")
+                .and_then(Value::as_bool)
+                .unwrap_or_default(),
+            accepted: doc
+                .get(table, "accepted")
+                .and_then(Value::as_str)
+                .map(String::from),
+        };
+
+        Self {
+            part_1: part("part_1"),
+            part_2: part("part_2"),
+        }
+    }
+
+    fn to_document(&self) -> Document {
+        let mut doc = Document::new();
+
+        for (table, record) in [("part_1", &self.part_1), ("part_2", &self.part_2)] {
+            if let Some(answer) = &record.answer {
+                doc.set(table, "answer", Value::String(answer.clone()));
+            }
+            if let Some((duration, samples)) = record.timing {
+                let nanos = i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+                let samples = i64::try_from(samples).unwrap_or(i64::MAX);
+                doc.set(table, "nanos", Value::Integer(nanos));
+                doc.set(table, "samples", Value::Integer(samples));
+            }
+            if let Some(parse) = record.parse {
+                let nanos = i64::try_from(parse.as_nanos()).unwrap_or(i64::MAX);
+                doc.set(table, "parse_nanos", Value::Integer(nanos));
+            }
+            if let Some(memory) = record.memory {
+                let bytes = i64::try_from(memory).unwrap_or(i64::MAX);
+                doc.set(table, "memory", Value::Integer(bytes));
+            }
+            if record.solved {
+                doc.set(table, "solved", Value::Boolean(true));
+            }
+            if let Some(accepted) = &record.accepted {
+                doc.set(table, "accepted", Value::String(accepted.clone()));
+            }
+        }
+
+        doc
+    }
+}
+
+fn get_record_path(day: Day) -> PathBuf {
+    config::get().data_path("answers", &format!("{day}.toml"))
+}
+
+/// Loads the record for a day. Days without a record yield an empty one.
+pub fn load(day: Day) -> DayRecord {
+    fs::read_to_string(get_record_path(day))
+        .ok()
+        .and_then(|s| Document::parse(&s).ok())
+        .map(|doc| DayRecord::from_document(&doc))
+        .unwrap_or_default()
+}
+
+pub fn save(day: Day, record: &DayRecord) -> Result<(), io::Error> {
+    let path = get_record_path(day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, record.to_document().to_string())
+}
+
+/// Stores the result of a run. The measurements are only replaced when the run was benched,
+/// the submission state is kept.
+pub fn record(day: Day, part: u8, run: PartRecord) -> Result<(), io::Error> {
+    let mut record = load(day);
+    let part_record = record.part_mut(part);
+
+    part_record.answer = run.answer;
+    if run.timing.is_some() {
+        part_record.timing = run.timing;
+        part_record.parse = run.parse;
+        part_record.memory = run.memory;
+    }
+
+    save(day, &record)
+}
+
+/// Marks a part as solved after the website accepted its answer.
+/// `accepted` is only known if the answer was checked, i.e. the part was not solved before.
+pub fn mark_solved(day: Day, part: u8, accepted: Option<&str>) -> Result<(), io::Error> {
+    let mut record = load(day);
+    let part_record = record.part_mut(part);
+    part_record.solved = true;
+    if let Some(accepted) = accepted {
+        part_record.accepted = Some(accepted.into());
+    }
+    save(day, &record)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{DayRecord, PartRecord};
+    use crate::template::toml_lite::Document;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = DayRecord {
+            part_1: PartRecord {
+                answer: Some("35".into()),
+                timing: Some((Duration::from_nanos(74130), 100)),
+                parse: Some(Duration::from_nanos(1200)),
+                memory: Some(4096),
+                solved: true,
+                accepted: Some("35".into()),
+            },
+            part_2: PartRecord {
+                answer: Some("multi\nline".into()),
+                timing: None,
+                parse: None,
+                memory: None,
+                solved: false,
+                accepted: None,
+            },
+        };
+
+        let doc = Document::parse(&record.to_document().to_string()).unwrap();
+        assert_eq!(DayRecord::from_document(&doc), record);
+    }
+}
@@ -1,38 +1,149 @@
-/// Wrapper module around the "aoc-cli" command-line.
+//! Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::Path,
     process::{Command, Output, Stdio},
+    thread,
 };
 
+use crate::template::aoc_client::{self, AocClient, SubmitVerdict};
+use crate::template::{config, session};
 use crate::Day;
 
 #[derive(Debug)]
 pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
-    IoError,
+    /// `aoc` is not installed or not on the `PATH`.
+    CommandNotFound(io::Error),
+    /// `aoc` exists but could not be started, e.g. because it is not executable.
+    CommandNotCallable(io::Error),
+    /// aoc-cli ran but failed. `code` is [`None`] if it was terminated by a signal.
+    /// The output holds everything aoc-cli printed.
+    BadExitStatus {
+        code: Option<i32>,
+        output: Output,
+    },
+    IoError(io::Error),
+}
+
+impl AocCommandError {
+    fn from_spawn_error(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::NotFound => AocCommandError::CommandNotFound(e),
+            _ => AocCommandError::CommandNotCallable(e),
+        }
+    }
+
+    /// What aoc-cli printed to stderr before failing.
+    pub fn stderr(&self) -> Option<String> {
+        match self {
+            AocCommandError::BadExitStatus { output, .. } => {
+                Some(String::from_utf8_lossy(&output.stderr).into_owned())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether aoc-cli failed because the session cookie is missing, invalid or expired.
+    pub fn is_session_error(&self) -> bool {
+        self.stderr().is_some_and(|stderr| {
+            let stderr = stderr.to_ascii_lowercase();
+            stderr.contains("session") || stderr.contains("log in")
+        })
+    }
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::CommandNotFound(_) => {
+                write!(f, "aoc-cli is not present in environment.")
+            }
+            AocCommandError::CommandNotCallable(e) => write!(f, "aoc-cli could not be called: {e}"),
+            AocCommandError::BadExitStatus { code, .. } => {
+                match code {
+                    Some(code) => write!(f, "aoc-cli exited with status {code}")?,
+                    None => write!(f, "aoc-cli was terminated by a signal")?,
+                }
+                let stderr = self.stderr().unwrap_or_default();
+                match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+                    Some(line) => write!(f, ": {}", line.trim()),
+                    None => write!(f, "."),
+                }
             }
-            AocCommandError::IoError => write!(f, "could not write output files to file system."),
+            AocCommandError::IoError(e) => write!(f, "could not read output of aoc-cli: {e}"),
         }
     }
 }
 
+impl std::error::Error for AocCommandError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AocCommandError::CommandNotFound(e)
+            | AocCommandError::CommandNotCallable(e)
+            | AocCommandError::IoError(e) => Some(e),
+            AocCommandError::BadExitStatus { .. } => None,
+        }
+    }
+}
+
+/// The aoc-cli backend of [`AocClient`].
+pub struct AocCli;
+
+impl AocClient for AocCli {
+    fn check(&self) -> Result<(), aoc_client::Error> {
+        Ok(check()?)
+    }
+
+    fn validate_session(&self) -> Result<(), aoc_client::Error> {
+        match calendar() {
+            Ok(_) => Ok(()),
+            Err(AocCommandError::BadExitStatus { .. }) => Err(aoc_client::Error::InvalidSession),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn download_input(&self, day: Day, path: &Path) -> Result<(), aoc_client::Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        download_input(day, path)?;
+        Ok(())
+    }
+
+    fn download_puzzle(&self, day: Day) -> Result<(), aoc_client::Error> {
+        download_puzzle(day)?;
+        Ok(())
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, aoc_client::Error> {
+        let output = submit(day, part, answer)?;
+        let message = String::from_utf8_lossy(&output.stdout);
+        SubmitVerdict::parse(&message)
+            .ok_or_else(|| aoc_client::Error::UnknownVerdict(message.trim().to_string()))
+    }
+
+    fn fetch_leaderboard(&self, id: u64) -> Result<String, aoc_client::Error> {
+        fetch_leaderboard(id)
+    }
+}
+
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
+    let output = Command::new("aoc")
         .arg("-V")
         .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+        .map_err(AocCommandError::from_spawn_error)?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(AocCommandError::BadExitStatus {
+            code: output.status.code(),
+            output,
+        })
+    }
 }
 
 pub fn read(day: Day) -> Result<Output, AocCommandError> {
@@ -51,27 +162,49 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// Prints the calendar of the configured year, which requires a valid session.
+pub fn calendar() -> Result<Output, AocCommandError> {
+    let mut args = session_args();
+    if let Some(year) = get_year() {
+        args.push("--year".into());
+        args.push(year.to_string());
+    }
+    args.push("calendar".into());
+    call_aoc_cli(&args)
+}
+
+pub fn download_input(day: Day, path: &Path) -> Result<Output, AocCommandError> {
+    let input_path = path.display().to_string();
 
     let args = build_args(
         "download",
         &[
             "--overwrite".into(),
+            "--input-only".into(),
             "--input-file".into(),
-            input_path.to_string(),
+            input_path,
+        ],
+        day,
+    );
+
+    call_aoc_cli(&args)
+}
+
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(day);
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
             "--puzzle-file".into(),
-            puzzle_path.to_string(),
+            puzzle_path,
         ],
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+    call_aoc_cli(&args)
 }
 
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
@@ -82,23 +215,71 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Quotes a value for a curl config file.
+fn curl_config_value(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// aoc-cli only prints leaderboards as rendered tables, so the JSON is fetched with `curl` instead.
+/// The session cookie is passed as a config file over stdin, as arguments are visible to other users.
+fn fetch_leaderboard(id: u64) -> Result<String, aoc_client::Error> {
+    let year = get_year().ok_or(aoc_client::Error::MissingYear)?;
+    let session = session::read_session().ok_or(aoc_client::Error::MissingSession)?;
+    let url = format!(
+        "{}/{year}/leaderboard/private/view/{id}.json",
+        config::get().client.base_url.trim_end_matches('/')
+    );
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| aoc_client::Error::Curl(format!("could not call curl: {e}")))?;
+
+    let config = format!(
+        "url = {}\ncookie = {}\n",
+        curl_config_value(&url),
+        curl_config_value(&format!("session={session}"))
+    );
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|e| aoc_client::Error::Curl(format!("could not pass the session: {e}")))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| aoc_client::Error::Curl(format!("could not call curl: {e}")))?;
+
+    if !output.status.success() {
+        let message = format!("curl exited with {}", output.status);
+        return Err(aoc_client::Error::Curl(message));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    config::get().puzzle_path(day).display().to_string()
 }
 
 fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+    config::get().year
+}
+
+/// Points aoc-cli to the session file of the active profile.
+fn session_args() -> Vec<String> {
+    match session::active_profile().and_then(|name| session::profile_file(&name)) {
+        Some(path) => vec!["--session-file".into(), path.display().to_string()],
+        None => vec![],
     }
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
+    cmd_args.append(&mut session_args());
 
     if let Some(year) = get_year() {
         cmd_args.push("--year".into());
@@ -110,18 +291,109 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
+/// Calls aoc-cli, forwarding its stdout and stderr to the terminal while capturing both into the returned [`Output`].
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+    let mut cmd = Command::new("aoc");
+    cmd.args(args);
+
+    // aoc-cli reads the cookie from this variable before looking at any file.
+    if let Ok(session) = env::var(session::SESSION_ENV) {
+        cmd.env("ADVENT_OF_CODE_SESSION", session.trim());
+    }
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(AocCommandError::from_spawn_error)?;
+
+    // stderr is drained on its own thread, so a full pipe cannot block aoc-cli while stdout is read.
+    let stderr_pipe = child.stderr.take();
+    let stderr_thread = thread::spawn(move || {
+        stderr_pipe.map_or(Ok(vec![]), |pipe| {
+            tee_lines(pipe, |line| eprintln!("{line}"))
+        })
+    });
+
+    let stdout = match child.stdout.take() {
+        Some(pipe) => tee_lines(pipe, |line| println!("{line}")),
+        None => Ok(vec![]),
+    };
+
+    let stderr = stderr_thread
+        .join()
+        .unwrap_or_else(|_| Err(io::Error::other("stderr reader panicked")));
+
+    let status = child.wait().map_err(AocCommandError::IoError)?;
+    let output = Output {
+        status,
+        stdout: stdout.map_err(AocCommandError::IoError)?,
+        stderr: stderr.map_err(AocCommandError::IoError)?,
+    };
 
     if output.status.success() {
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(AocCommandError::BadExitStatus {
+            code: output.status.code(),
+            output,
+        })
+    }
+}
+
+/// Prints every line of `pipe` with `print` and returns everything that was read.
+fn tee_lines(pipe: impl Read, print: impl Fn(&str)) -> Result<Vec<u8>, io::Error> {
+    let mut captured = vec![];
+    for line in BufReader::new(pipe).lines() {
+        let line = line?;
+        print(&line);
+        captured.extend_from_slice(line.as_bytes());
+        captured.push(b'\n');
+    }
+    Ok(captured)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{error::Error, io, process::Command};
+
+    use super::{curl_config_value, AocCommandError};
+
+    #[test]
+    fn quotes_curl_config_values() {
+        assert_eq!(curl_config_value("session=53616c"), "\"session=53616c\"");
+        assert_eq!(curl_config_value("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+
+    #[test]
+    fn keeps_the_cause_of_spawn_errors() {
+        let e = Command::new("aoc-cli-that-does-not-exist")
+            .spawn()
+            .map_err(AocCommandError::from_spawn_error)
+            .unwrap_err();
+
+        assert!(matches!(e, AocCommandError::CommandNotFound(_)));
+        let source = e.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn describes_bad_exit_status() {
+        let output = Command::new("sh")
+            .args(["-c", "echo 'Error: invalid session cookie' >&2; exit 2"])
+            .output()
+            .unwrap();
+        let e = AocCommandError::BadExitStatus {
+            code: output.status.code(),
+            output,
+        };
+
+        assert_eq!(
+            e.to_string(),
+            "aoc-cli exited with status 2: Error: invalid session cookie"
+        );
+        assert!(e.is_session_error());
     }
 }
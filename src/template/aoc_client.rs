@@ -0,0 +1,465 @@
+//! Abstraction over the ways the template talks to the Advent of Code website.
+//! The backend is selected with `client.backend` in `aoc.toml`.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{
+    aoc_cli::{self, AocCommandError},
+    config::{self, Backend, Config},
+    downloads::{self, InvalidInput},
+    http,
+    native_client::NativeClient,
+};
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    Command(AocCommandError),
+    Curl(String),
+    Http(http::Error),
+    Status(u16),
+    MissingSession,
+    InvalidSession,
+    MissingYear,
+    UnknownVerdict(String),
+    /// The downloaded input was rejected and moved to the contained path.
+    InvalidInput(InvalidInput, PathBuf),
+    IO(io::Error),
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::Command(e)
+    }
+}
+
+impl From<http::Error> for Error {
+    fn from(e: http::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Command(AocCommandError::CommandNotFound(_)) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::Command(e) if e.is_session_error() => write!(
+                f,
+                "failed to call aoc-cli: {e}\nThe session cookie might have expired, set a new one with \"cargo session set <cookie>\"."
+            ),
+            Error::Command(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::Curl(message) => write!(f, "failed to fetch with curl: {message}."),
+            Error::Http(e) => write!(f, "{e}"),
+            Error::Status(status) => {
+                write!(f, "the website responded with http status {status}.")
+            }
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found. Set one with \"cargo session set <cookie>\" or the `AOC_SESSION` environment variable."
+            ),
+            Error::InvalidSession => write!(
+                f,
+                "the session cookie was rejected. It might have expired, set a new one with \"cargo session set <cookie>\"."
+            ),
+            Error::MissingYear => write!(f, "no year configured. Set `AOC_YEAR` or `year` in aoc.toml."),
+            Error::UnknownVerdict(message) => {
+                write!(f, "could not interpret the response to the submission: {message:?}")
+            }
+            Error::InvalidInput(reason, path) => write!(
+                f,
+                "the downloaded input is not valid: {reason}. It was moved to \"{}\".",
+                path.display()
+            ),
+            Error::IO(e) => write!(f, "could not write output files to file system: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Command(e) => Some(e),
+            Error::Http(e) => Some(e),
+            Error::IO(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of submitting an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitVerdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently. Contains the time left to wait.
+    RateLimited(Duration),
+    /// The part was solved before, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl SubmitVerdict {
+    /// Interprets the message the website responds with after a submission.
+    pub fn parse(message: &str) -> Option<Self> {
+        let message = message.to_ascii_lowercase();
+
+        if message.contains("that's the right answer") {
+            Some(Self::Correct)
+        } else if message.contains("that's not the right answer") {
+            if message.contains("too high") {
+                Some(Self::TooHigh)
+            } else if message.contains("too low") {
+                Some(Self::TooLow)
+            } else {
+                Some(Self::Wrong)
+            }
+        } else if message.contains("you gave an answer too recently") {
+            Some(Self::RateLimited(parse_wait_time(&message)))
+        } else if message.contains("you don't seem to be solving the right level") {
+            Some(Self::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the submission should count as a failed run.
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Self::Correct | Self::AlreadySolved)
+    }
+}
+
+impl Display for SubmitVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer!"),
+            Self::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Self::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Self::Wrong => write!(f, "That's not the right answer."),
+            Self::RateLimited(wait) => write!(
+                f,
+                "You gave an answer too recently, {}s left to wait.",
+                wait.as_secs()
+            ),
+            Self::AlreadySolved => write!(f, "This part is already solved."),
+        }
+    }
+}
+
+/// Parses wait times like "you have 42s left to wait" or "you have 1m 5s left to wait".
+fn parse_wait_time(message: &str) -> Duration {
+    let Some(start) = message.find("you have ") else {
+        return Duration::ZERO;
+    };
+    let rest = &message[start + "you have ".len()..];
+    let end = rest.find(" left").unwrap_or(rest.len());
+
+    let secs = rest[..end]
+        .split_whitespace()
+        .filter_map(|token| {
+            let unit_at = token.find(|c: char| !c.is_ascii_digit())?;
+            let value: u64 = token[..unit_at].parse().ok()?;
+            match &token[unit_at..] {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum();
+
+    Duration::from_secs(secs)
+}
+
+pub trait AocClient {
+    /// Verifies that the client is usable, e.g. that aoc-cli is installed.
+    fn check(&self) -> Result<(), Error>;
+
+    /// Checks that the website accepts the session cookie.
+    fn validate_session(&self) -> Result<(), Error>;
+
+    /// Downloads the input of a day to `path`, replacing an existing file.
+    fn download_input(&self, day: Day, path: &Path) -> Result<(), Error>;
+
+    /// Downloads the puzzle description of a day into the data folder, replacing an existing file.
+    fn download_puzzle(&self, day: Day) -> Result<(), Error>;
+
+    /// Downloads the puzzle description of a day and records the time of the download.
+    fn refresh_puzzle(&self, day: Day) -> Result<(), Error> {
+        self.download_puzzle(day)?;
+
+        let config = config::get();
+        let mut record = downloads::load(config, day);
+        record.puzzle = Some(downloads::now());
+        downloads::save(config, day, &record)?;
+        Ok(())
+    }
+
+    /// Downloads the input and puzzle description of a day into the data folder.
+    /// An existing input is kept unless `force` is set, the puzzle is always refreshed
+    /// because part two only shows up once part one is solved.
+    fn download(&self, day: Day, force: bool) -> Result<(), Error> {
+        let config = config::get();
+        let input_path = config.input_path(day);
+        let fetched_input = download_day(self, config, day, force)?;
+
+        println!("---");
+        if fetched_input {
+            println!(
+                "🎄 Successfully wrote input to \"{}\".",
+                input_path.display()
+            );
+        } else {
+            println!(
+                "🎄 Kept existing input \"{}\", pass --force to download it again.",
+                input_path.display()
+            );
+        }
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            config.puzzle_path(day).display()
+        );
+        Ok(())
+    }
+
+    /// Submits the answer to one part of a day.
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, Error>;
+
+    /// Fetches the JSON of a private leaderboard of the configured year.
+    fn fetch_leaderboard(&self, id: u64) -> Result<String, Error>;
+}
+
+/// Downloads what [`AocClient::download`] needs into the data folder of `config` and records
+/// the time of each download. Returns whether the input was downloaded.
+fn download_day(
+    client: &(impl AocClient + ?Sized),
+    config: &Config,
+    day: Day,
+    force: bool,
+) -> Result<bool, Error> {
+    let input_path = config.input_path(day);
+    let mut record = downloads::load(config, day);
+
+    let fetch_input = force || !downloads::has_input(config, day);
+    if fetch_input {
+        let download = downloads::download_path(&input_path);
+        if let Err(e) = client.download_input(day, &download) {
+            let _ = fs::remove_file(&download);
+            return Err(e);
+        }
+        install_input(&download, &input_path)?;
+        record.input = Some(downloads::now());
+        downloads::save(config, day, &record)?;
+    }
+
+    client.download_puzzle(day)?;
+    record.puzzle = Some(downloads::now());
+    downloads::save(config, day, &record)?;
+
+    Ok(fetch_input)
+}
+
+/// Moves a downloaded input over the input file once it is validated.
+/// A rejected download is quarantined, so an existing input survives it.
+fn install_input(download: &Path, input_path: &Path) -> Result<(), Error> {
+    let input = fs::read_to_string(download).unwrap_or_default();
+    if let Err(reason) = downloads::validate_input(&input) {
+        let rejected = downloads::quarantine(download, input_path)?;
+        return Err(Error::InvalidInput(reason, rejected));
+    }
+
+    fs::rename(download, input_path)?;
+    Ok(())
+}
+
+/// Returns the client configured for this workspace.
+pub fn get() -> Box<dyn AocClient> {
+    match config::get().client.backend {
+        Backend::AocCli => Box::new(aoc_cli::AocCli),
+        Backend::Native => Box::new(NativeClient::from_config()),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{cell::Cell, env, fs, path::Path, process, time::Duration};
+
+    use super::{download_day, install_input, AocClient, Error, SubmitVerdict};
+    use crate::template::{config::Config, downloads};
+    use crate::{day, Day};
+
+    /// Serves a fixed input and counts the downloads.
+    struct StubClient {
+        input: &'static str,
+        input_downloads: Cell<usize>,
+        puzzle_downloads: Cell<usize>,
+    }
+
+    impl StubClient {
+        fn new(input: &'static str) -> Self {
+            Self {
+                input,
+                input_downloads: Cell::new(0),
+                puzzle_downloads: Cell::new(0),
+            }
+        }
+    }
+
+    impl AocClient for StubClient {
+        fn check(&self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn validate_session(&self) -> Result<(), Error> {
+            Ok(())
+        }
+
+        fn download_input(&self, _day: Day, path: &Path) -> Result<(), Error> {
+            self.input_downloads.set(self.input_downloads.get() + 1);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, self.input)?;
+            Ok(())
+        }
+
+        fn download_puzzle(&self, _day: Day) -> Result<(), Error> {
+            self.puzzle_downloads.set(self.puzzle_downloads.get() + 1);
+            Ok(())
+        }
+
+        fn submit(&self, _day: Day, _part: u8, _answer: &str) -> Result<SubmitVerdict, Error> {
+            unimplemented!()
+        }
+
+        fn fetch_leaderboard(&self, _id: u64) -> Result<String, Error> {
+            unimplemented!()
+        }
+    }
+
+    fn temp_config(name: &str) -> Config {
+        let dir = env::temp_dir().join(format!("aoc-download-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut config = Config::default();
+        config.data_dir = dir;
+        config
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let cases = [
+            ("That's the right answer! You are one gold star closer.", SubmitVerdict::Correct),
+            ("That's not the right answer; your answer is too high. Please wait one minute.", SubmitVerdict::TooHigh),
+            ("That's not the right answer; your answer is too low.", SubmitVerdict::TooLow),
+            ("That's not the right answer. If you're stuck, make sure you're using the full input data.", SubmitVerdict::Wrong),
+            ("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 42s left to wait.", SubmitVerdict::RateLimited(Duration::from_secs(42))),
+            ("You gave an answer too recently; You have 4m 12s left to wait.", SubmitVerdict::RateLimited(Duration::from_secs(252))),
+            ("You don't seem to be solving the right level. Did you already complete it?", SubmitVerdict::AlreadySolved),
+        ];
+
+        for (message, verdict) in cases {
+            assert_eq!(SubmitVerdict::parse(message), Some(verdict), "{message}");
+        }
+
+        assert_eq!(SubmitVerdict::parse("Please log in."), None);
+    }
+
+    #[test]
+    fn keeps_the_input_when_a_download_is_rejected() {
+        let dir = env::temp_dir().join(format!("aoc-install-input-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input_path = dir.join("01.txt");
+        let download = dir.join("01.txt.download");
+
+        fs::write(&input_path, "1abc2\n").unwrap();
+        fs::write(&download, "Please log in to get your puzzle input.\n").unwrap();
+        let result = install_input(&download, &input_path);
+
+        assert!(matches!(result, Err(Error::InvalidInput(_, _))));
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "1abc2\n");
+        assert!(dir.join("01.txt.rejected").exists());
+        assert!(!download.exists());
+
+        fs::write(&download, "pqr3stu8vwx\n").unwrap();
+        install_input(&download, &input_path).unwrap();
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "pqr3stu8vwx\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn downloads_missing_inputs_and_records_the_time() {
+        let config = temp_config("missing");
+        let client = StubClient::new("1abc2\n");
+        let start = downloads::now();
+
+        assert!(download_day(&client, &config, day!(1), false).unwrap());
+        assert_eq!(
+            fs::read_to_string(config.input_path(day!(1))).unwrap(),
+            "1abc2\n"
+        );
+        assert_eq!(client.puzzle_downloads.get(), 1);
+
+        let record = downloads::load(&config, day!(1));
+        assert!(record.input.is_some_and(|t| t >= start));
+        assert!(record.puzzle.is_some_and(|t| t >= start));
+        assert!(!downloads::download_path(&config.input_path(day!(1))).exists());
+
+        fs::remove_dir_all(&config.data_dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_existing_inputs_unless_forced() {
+        let config = temp_config("force");
+        let input_path = config.input_path(day!(1));
+        fs::create_dir_all(input_path.parent().unwrap()).unwrap();
+        fs::write(&input_path, "old\n").unwrap();
+        let client = StubClient::new("new\n");
+
+        assert!(!download_day(&client, &config, day!(1), false).unwrap());
+        assert_eq!(client.input_downloads.get(), 0);
+        assert_eq!(client.puzzle_downloads.get(), 1);
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "old\n");
+        let record = downloads::load(&config, day!(1));
+        assert_eq!(record.input, None);
+        assert!(record.puzzle.is_some());
+
+        assert!(download_day(&client, &config, day!(1), true).unwrap());
+        assert_eq!(client.input_downloads.get(), 1);
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "new\n");
+        assert!(downloads::load(&config, day!(1)).input.is_some());
+
+        fs::remove_dir_all(&config.data_dir).unwrap();
+    }
+
+    #[test]
+    fn quarantines_rejected_downloads() {
+        let config = temp_config("rejected");
+        let input_path = config.input_path(day!(1));
+        fs::create_dir_all(input_path.parent().unwrap()).unwrap();
+        fs::write(&input_path, "old\n").unwrap();
+        let client = StubClient::new("Please log in to get your puzzle input.\n");
+
+        let result = download_day(&client, &config, day!(1), true);
+
+        assert!(matches!(result, Err(Error::InvalidInput(_, _))));
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "old\n");
+        assert!(input_path.with_extension("txt.rejected").exists());
+        // neither the input nor the puzzle counts as downloaded.
+        assert_eq!(client.puzzle_downloads.get(), 0);
+        assert_eq!(downloads::load(&config, day!(1)), Default::default());
+
+        fs::remove_dir_all(&config.data_dir).unwrap();
+    }
+}
@@ -0,0 +1,204 @@
+//! Renders benchmark timings as an SVG bar chart for the readme.
+//! Bars are log-scaled, as execution times of different days easily span several orders of magnitude.
+//! Each bar stacks the time of part one and part two of a day.
+use std::fmt::Write;
+
+use crate::template::readme_benchmarks::{parse_duration_nanos, Timings};
+
+const BAR_WIDTH: f64 = 20.0;
+const BAR_GAP: f64 = 10.0;
+const MARGIN_LEFT: f64 = 56.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 36.0;
+const MARGIN_BOTTOM: f64 = 28.0;
+const PLOT_HEIGHT: f64 = 180.0;
+
+const COLOR_PART_1: &str = "#4e79a7";
+const COLOR_PART_2: &str = "#f28e2b";
+
+/// The nanoseconds of both parts of a day. Missing parts are `0`.
+fn part_nanos(timing: &Timings) -> [f64; 2] {
+    [&timing.part_1, &timing.part_2].map(|part| {
+        part.as_deref()
+            .and_then(parse_duration_nanos)
+            .unwrap_or_default()
+    })
+}
+
+/// Labels for powers of ten, e.g. `100ns` or `10ms`.
+fn format_decade(exponent: i32) -> String {
+    let (unit, base) = match exponent {
+        i32::MIN..=2 => ("ns", 0),
+        3..=5 => ("µs", 3),
+        6..=8 => ("ms", 6),
+        _ => ("s", 9),
+    };
+    format!(
+        "{}{unit}",
+        10_u64.pow((exponent - base).max(0).unsigned_abs())
+    )
+}
+
+/// The decades spanned by the y axis. The lower bound is the fastest part, the upper one the slowest day.
+#[allow(clippy::cast_possible_truncation)]
+fn axis_range(days: &[[f64; 2]]) -> (i32, i32) {
+    let min = days
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|n| *n > 0.0)
+        .fold(f64::INFINITY, f64::min);
+    let max = days.iter().map(|[a, b]| a + b).fold(0.0, f64::max);
+
+    if !min.is_finite() || max <= 0.0 {
+        return (0, 3);
+    }
+
+    let lo = (min.log10().floor() as i32).max(0);
+    let hi = (max.log10().ceil() as i32).max(lo + 1);
+    (lo, hi)
+}
+
+/// Renders the chart. Days without any timing are left out.
+pub fn render(timings: &[Timings]) -> String {
+    let days: Vec<(&Timings, [f64; 2])> = timings
+        .iter()
+        .map(|t| (t, part_nanos(t)))
+        .filter(|(_, [a, b])| a + b > 0.0)
+        .collect();
+
+    let parts: Vec<[f64; 2]> = days.iter().map(|(_, parts)| *parts).collect();
+    let (lo, hi) = axis_range(&parts);
+    let scale = PLOT_HEIGHT / f64::from(hi - lo);
+    let bottom = MARGIN_TOP + PLOT_HEIGHT;
+    let y = |nanos: f64| bottom - (nanos.max(1.0).log10() - f64::from(lo)).max(0.0) * scale;
+
+    #[allow(clippy::cast_precision_loss)]
+    let plot_width = (days.len().max(1) as f64) * (BAR_WIDTH + BAR_GAP);
+    let width = MARGIN_LEFT + plot_width + MARGIN_RIGHT;
+    let height = bottom + MARGIN_BOTTOM;
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        out,
+        r##"<rect width="100%" height="100%" fill="#ffffff"/>"##
+    );
+
+    let legend_x = MARGIN_LEFT;
+    let _ = writeln!(
+        out,
+        r#"<rect x="{legend_x}" y="10" width="10" height="10" fill="{COLOR_PART_1}"/><text x="{}" y="19">Part 1</text>"#,
+        legend_x + 14.0
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect x="{}" y="10" width="10" height="10" fill="{COLOR_PART_2}"/><text x="{}" y="19">Part 2</text>"#,
+        legend_x + 64.0,
+        legend_x + 78.0
+    );
+
+    for exponent in lo..=hi {
+        let line_y = bottom - f64::from(exponent - lo) * scale;
+        let _ = writeln!(
+            out,
+            r##"<line x1="{MARGIN_LEFT}" y1="{line_y}" x2="{}" y2="{line_y}" stroke="#dddddd"/><text x="{}" y="{}" text-anchor="end">{}</text>"##,
+            MARGIN_LEFT + plot_width,
+            MARGIN_LEFT - 6.0,
+            line_y + 4.0,
+            format_decade(exponent)
+        );
+    }
+
+    for (i, (timing, [part_1, part_2])) in days.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = MARGIN_LEFT + BAR_GAP / 2.0 + (i as f64) * (BAR_WIDTH + BAR_GAP);
+        let day = timing.day.into_inner();
+
+        // segments are stacked on the cumulative time, so the top of a bar is the total of the day.
+        let segments = [
+            (0.0, *part_1, COLOR_PART_1, &timing.part_1),
+            (*part_1, part_1 + part_2, COLOR_PART_2, &timing.part_2),
+        ];
+        for (from, to, color, label) in segments {
+            let (Some(label), true) = (label, to > from) else {
+                continue;
+            };
+            let top = y(to);
+            let base = if from > 0.0 { y(from) } else { bottom };
+            let _ = writeln!(
+                out,
+                r#"<rect x="{x}" y="{top}" width="{BAR_WIDTH}" height="{}" fill="{color}"><title>Day {day}: {label}</title></rect>"#,
+                (base - top).max(0.0)
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            r#"<text x="{}" y="{}" text-anchor="middle">{day}</text>"#,
+            x + BAR_WIDTH / 2.0,
+            bottom + 16.0
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{axis_range, format_decade, render};
+    use crate::day;
+    use crate::template::readme_benchmarks::Timings;
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(2), "100ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(6), "1ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn spans_all_timings() {
+        assert_eq!(axis_range(&[[500.0, 0.0], [2e6, 3e6]]), (2, 7));
+        assert_eq!(axis_range(&[]), (0, 3));
+        assert_eq!(axis_range(&[[1000.0, 0.0]]), (3, 4));
+    }
+
+    #[test]
+    fn renders_stacked_bars() {
+        let timings = vec![
+            Timings {
+                day: day!(1),
+                part_1: Some("10ms".into()),
+                part_2: Some("20ms".into()),
+                total_nanos: 3e+7,
+            },
+            Timings {
+                day: day!(3),
+                part_1: Some("1.5µs".into()),
+                part_2: None,
+                total_nanos: 1500.0,
+            },
+            Timings {
+                day: day!(4),
+                part_1: None,
+                part_2: None,
+                total_nanos: 0.0,
+            },
+        ];
+
+        let svg = render(&timings);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>Day 1:").count(), 2);
+        assert_eq!(svg.matches("<title>Day 3:").count(), 1);
+        assert!(!svg.contains("Day 4"));
+        assert!(svg.contains(">1µs</text>") && svg.contains(">100ms</text>"));
+    }
+}
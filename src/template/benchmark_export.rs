@@ -0,0 +1,314 @@
+//! Writes the results of `cargo all --time` to a file for reporting outside of the readme.
+//! The format is chosen from the extension of the path: `.csv`, `.json` or `.html`.
+use std::{
+    fmt::{Display, Write},
+    fs, io,
+    path::Path,
+    time::Duration,
+};
+
+use crate::template::json_lite::Value;
+use crate::template::readme_benchmarks::Timings;
+use crate::template::{answers, config};
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownFormat(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownFormat(extension) => write!(
+                f,
+                "unknown export format \"{extension}\", use a path ending in .csv, .json or .html"
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(e) => Some(e),
+            Error::UnknownFormat(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Html,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self, Error> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match extension.as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "html" | "htm" => Ok(Format::Html),
+            _ => Err(Error::UnknownFormat(extension)),
+        }
+    }
+}
+
+/// The result of a single part, as recorded by the runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportRow {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub nanos: Option<u128>,
+    pub samples: Option<u128>,
+}
+
+/// Builds the rows for the days of a run from their answer records, which hold the exact values behind `timings`.
+pub fn collect_rows(timings: &[Timings]) -> Vec<ExportRow> {
+    timings
+        .iter()
+        .flat_map(|timing| {
+            let record = answers::load(timing.day);
+            [1, 2].map(|part| {
+                let part_record = record.part(part);
+                ExportRow {
+                    day: timing.day,
+                    part,
+                    answer: part_record.answer.clone(),
+                    nanos: part_record.timing.map(|(d, _)| d.as_nanos()),
+                    samples: part_record.timing.map(|(_, samples)| samples),
+                }
+            })
+        })
+        .collect()
+}
+
+fn total_nanos(rows: &[ExportRow]) -> u128 {
+    rows.iter().filter_map(|r| r.nanos).sum()
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn render_csv(rows: &[ExportRow]) -> String {
+    let mut out = String::from("day,part,answer,nanos,samples\n");
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{}",
+            row.day.into_inner(),
+            row.part,
+            csv_field(row.answer.as_deref().unwrap_or_default()),
+            row.nanos.map(|n| n.to_string()).unwrap_or_default(),
+            row.samples.map(|n| n.to_string()).unwrap_or_default(),
+        );
+    }
+    out
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn render_json(rows: &[ExportRow], year: Option<u16>) -> String {
+    let number = |n: Option<u128>| n.map_or(Value::Null, |n| Value::Number(n as f64));
+
+    let results = rows
+        .iter()
+        .map(|row| {
+            Value::Object(vec![
+                ("day".into(), Value::Number(row.day.into_inner().into())),
+                ("part".into(), Value::Number(row.part.into())),
+                ("answer".into(), row.answer.clone().into()),
+                ("nanos".into(), number(row.nanos)),
+                ("samples".into(), number(row.samples)),
+            ])
+        })
+        .collect();
+
+    let document = Value::Object(vec![
+        ("year".into(), year.map(f64::from).into()),
+        ("total_nanos".into(), number(Some(total_nanos(rows)))),
+        ("results".into(), Value::Array(results)),
+    ]);
+    format!("{document}\n")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_nanos(nanos: u128) -> String {
+    let nanos = u64::try_from(nanos).unwrap_or(u64::MAX);
+    format!("{:.1?}", Duration::from_nanos(nanos))
+}
+
+fn render_html(rows: &[ExportRow], year: Option<u16>) -> String {
+    let title = match year {
+        Some(year) => format!("Advent of Code {year} benchmarks"),
+        None => "Advent of Code benchmarks".into(),
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(out, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>");
+    let _ = writeln!(out, "<meta charset=\"utf-8\">\n<title>{title}</title>");
+    let _ = writeln!(
+        out,
+        "<style>\n\
+         body {{ font-family: system-ui, sans-serif; margin: 2rem; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         th, td {{ padding: 0.25rem 0.75rem; border-bottom: 1px solid #ddd; text-align: right; }}\n\
+         td.answer {{ font-family: monospace; text-align: left; white-space: pre; }}\n\
+         </style>\n</head>\n<body>"
+    );
+    let _ = writeln!(out, "<h1>{title}</h1>\n<table>");
+    let _ = writeln!(
+        out,
+        "<tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th><th>Nanoseconds</th><th>Samples</th></tr>"
+    );
+
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td class=\"answer\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            row.day.into_inner(),
+            row.part,
+            escape_html(row.answer.as_deref().unwrap_or("-")),
+            row.nanos.map_or_else(|| "-".into(), format_nanos),
+            row.nanos.map(|n| n.to_string()).unwrap_or_default(),
+            row.samples.map(|n| n.to_string()).unwrap_or_default(),
+        );
+    }
+
+    let _ = writeln!(
+        out,
+        "</table>\n<p><strong>Total: {}</strong></p>\n</body>\n</html>",
+        format_nanos(total_nanos(rows))
+    );
+    out
+}
+
+pub fn render(format: Format, rows: &[ExportRow], year: Option<u16>) -> String {
+    match format {
+        Format::Csv => render_csv(rows),
+        Format::Json => render_json(rows, year),
+        Format::Html => render_html(rows, year),
+    }
+}
+
+/// Writes the results of a run to `path`, creating parent directories as needed.
+pub fn export(path: &Path, timings: &[Timings]) -> Result<(), Error> {
+    let format = Format::from_path(path)?;
+    let content = render(format, &collect_rows(timings), config::get().year);
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{render, ExportRow, Format};
+    use crate::day;
+    use crate::template::json_lite::Value;
+
+    fn get_mock_rows() -> Vec<ExportRow> {
+        vec![
+            ExportRow {
+                day: day!(1),
+                part: 1,
+                answer: Some("142".into()),
+                nanos: Some(74_130),
+                samples: Some(100),
+            },
+            ExportRow {
+                day: day!(1),
+                part: 2,
+                answer: Some("a, \"b\"\n<c>".into()),
+                nanos: None,
+                samples: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn detects_formats() {
+        assert_eq!(
+            Format::from_path(Path::new("out/a.CSV")).unwrap(),
+            Format::Csv
+        );
+        assert_eq!(
+            Format::from_path(Path::new("a.json")).unwrap(),
+            Format::Json
+        );
+        assert_eq!(
+            Format::from_path(Path::new("a.html")).unwrap(),
+            Format::Html
+        );
+        assert!(Format::from_path(Path::new("a.txt")).is_err());
+        assert!(Format::from_path(Path::new("report")).is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        assert_eq!(
+            render(Format::Csv, &get_mock_rows(), None),
+            "day,part,answer,nanos,samples\n1,1,142,74130,100\n1,2,\"a, \"\"b\"\"\n<c>\",,\n"
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(Format::Json, &get_mock_rows(), Some(2023));
+        let value = Value::parse(&json).unwrap();
+
+        assert_eq!(value.get("year").and_then(Value::as_u64), Some(2023));
+        assert_eq!(
+            value.get("total_nanos").and_then(Value::as_u64),
+            Some(74_130)
+        );
+        let results = value.get("results").and_then(Value::as_array).unwrap();
+        assert_eq!(results[0].get("samples").and_then(Value::as_u64), Some(100));
+        assert_eq!(results[1].get("nanos"), Some(&Value::Null));
+        assert_eq!(
+            results[1].get("answer").and_then(Value::as_str),
+            Some("a, \"b\"\n<c>")
+        );
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = render(Format::Html, &get_mock_rows(), Some(2023));
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Advent of Code 2023 benchmarks</title>"));
+        assert!(html.contains("<td>74.1µs</td><td>74130</td><td>100</td>"));
+        assert!(html.contains("&quot;b&quot;\n&lt;c&gt;"));
+        assert!(html.contains("<strong>Total: 74.1µs</strong>"));
+    }
+}
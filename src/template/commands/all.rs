@@ -1,39 +1,381 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io,
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
 use crate::template::{
+    answers::{self, DayRecord},
+    benchmark_export::{self, Format},
+    config,
     readme_benchmarks::{self, Timings},
+    runner::PartRun,
+    test_report::{self, Outcome, Report, TestCase},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+/// The outcome of a day in a run of `all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayStatus {
+    Passed,
+    /// The solution could not be run, e.g. because it does not compile or its input is missing.
+    Failed,
+    Panicked,
+    /// A part returned something else than the answer the website accepted before.
+    WrongAnswer,
+    NotScaffolded,
+}
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+impl DayStatus {
+    /// Whether the day fails the run. Days that were not scaffolded yet do not.
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Failed | Self::Panicked | Self::WrongAnswer)
+    }
+}
+
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Panicked => "panicked",
+            Self::WrongAnswer => "wrong answer",
+            Self::NotScaffolded => "not scaffolded",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// The result of running the solution of a day.
+struct DayRun {
+    day: Day,
+    status: DayStatus,
+    /// The output of the solution, if it was buffered while other days were running.
+    output: String,
+    /// The errors of the solution, including panic messages, if they were buffered like the output.
+    errors: String,
+    /// Timings of the benchmark table, only set if both parts ran to completion.
+    timing: Option<Timings>,
+    /// The results of part one and part two, [`None`] for parts that did not run to completion.
+    parts: [Option<PartRun>; 2],
+    /// The message of the panic, if the solution panicked.
+    panic: Option<String>,
+}
+
+impl DayRun {
+    fn new(day: Day, status: DayStatus) -> Self {
+        Self {
+            day,
+            status,
+            output: String::new(),
+            errors: String::new(),
+            timing: None,
+            parts: [None, None],
+            panic: None,
         }
+    }
+}
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+/// Runs the solution of a day with `run` and compares the answers with the accepted ones.
+fn run_day(day: Day, is_buffered: bool, run: impl Fn(Day, bool) -> DayRun) -> DayRun {
+    let mut run = run(day, is_buffered);
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // the runner records the answers of both parts, in this process or in the solution binary.
+    if run.status == DayStatus::Passed && !wrong_parts(&answers::load(day)).is_empty() {
+        run.status = DayStatus::WrongAnswer;
+    }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+    run
+}
+
+/// The test cases of the parts of a day for reports.
+fn test_cases(run: &DayRun, record: &DayRecord) -> [TestCase; 2] {
+    [1, 2].map(|part| {
+        let result = run.parts[usize::from(part - 1)].as_ref();
+        let accepted = record.part(part).accepted.as_deref();
+
+        let outcome = match (run.status, result) {
+            (DayStatus::NotScaffolded, _) => Outcome::Skipped(run.status.to_string()),
+            (_, Some(PartRun { answer: None, .. })) => Outcome::Failed("returned None".into()),
+            (
+                _,
+                Some(PartRun {
+                    answer: Some(answer),
+                    ..
+                }),
+            ) => match accepted {
+                Some(accepted) if accepted != answer => {
+                    Outcome::Failed(format!("returned {answer}, expected {accepted}"))
+                }
+                _ => Outcome::Passed,
+            },
+            (DayStatus::Panicked, None) => Outcome::Failed(match &run.panic {
+                Some(message) => format!("panicked: {message}"),
+                None => "panicked".into(),
+            }),
+            (_, None) => Outcome::Failed("could not be run".into()),
+        };
+
+        TestCase {
+            day: run.day,
+            part,
+            answer: result.and_then(|r| r.answer.clone()),
+            duration: result.map(|r| r.duration),
+            outcome,
+        }
+    })
+}
+
+/// The parts whose last answer differs from the one the website accepted.
+fn wrong_parts(record: &DayRecord) -> Vec<u8> {
+    [1, 2]
+        .into_iter()
+        .filter(|part| {
+            let record = record.part(*part);
+            record
+                .accepted
+                .as_ref()
+                .is_some_and(|accepted| record.answer.as_ref() != Some(accepted))
+        })
+        .collect()
+}
+
+fn print_header(day: Day) {
+    if day > 1 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+fn print_status(run: &DayRun) {
+    match run.status {
+        DayStatus::Passed => {}
+        DayStatus::NotScaffolded => println!("Not scaffolded."),
+        DayStatus::Failed => println!("{ANSI_BOLD}Failed.{ANSI_RESET}"),
+        DayStatus::Panicked => println!("{ANSI_BOLD}Panicked.{ANSI_RESET}"),
+        DayStatus::WrongAnswer => {
+            let record = answers::load(run.day);
+            for part in wrong_parts(&record) {
+                println!(
+                    "{ANSI_BOLD}Wrong answer for part {part}{ANSI_RESET}, the accepted answer is {}.",
+                    record.part(part).accepted.as_deref().unwrap_or_default()
+                );
+            }
+        }
+    }
+}
+
+/// A line like `3 passed, 1 panicked, 21 not scaffolded`, followed by the failed days if there are any.
+fn summarize(statuses: &[(Day, DayStatus)]) -> String {
+    let order = [
+        DayStatus::Passed,
+        DayStatus::WrongAnswer,
+        DayStatus::Panicked,
+        DayStatus::Failed,
+        DayStatus::NotScaffolded,
+    ];
+
+    let counts: Vec<String> = order
+        .iter()
+        .filter_map(|status| {
+            let count = statuses.iter().filter(|(_, s)| s == status).count();
+            (count > 0).then(|| format!("{count} {status}"))
+        })
+        .collect();
+
+    let failures: Vec<String> = statuses
+        .iter()
+        .filter(|(_, status)| status.is_failure())
+        .map(|(day, status)| format!("{day} ({status})"))
+        .collect();
+
+    let mut summary = counts.join(", ");
+    if !failures.is_empty() {
+        summary.push_str(&format!("\nFailed days: {}", failures.join(", ")));
+    }
+    summary
+}
+
+/// Runs `run` for every day on `jobs` threads.
+/// Results are passed to `report` in the order of `days`, each as soon as all previous days are done.
+/// No further days are started once `report` returns `false`.
+fn run_parallel<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut report: impl FnMut(T) -> bool,
+) {
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (next, stop, run) = (&next, &stop, &run);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(day) = days.get(index) else {
+                        break;
+                    };
+                    if tx.send((index, run(*day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut reported = 0;
+        'results: for (index, result) in rx {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&reported) {
+                reported += 1;
+                if !report(result) {
+                    stop.store(true, Ordering::Relaxed);
+                    break 'results;
+                }
+            }
         }
     });
+}
+
+pub fn handle(
+    is_release: bool,
+    is_timed: bool,
+    export: Option<PathBuf>,
+    jobs: usize,
+    fail_fast: bool,
+    reports: &[Report],
+) {
+    if let Some(path) = &export {
+        if !is_timed {
+            eprintln!("--export requires --time, as it writes benchmark results.");
+            process::exit(1);
+        }
+        if let Err(e) = Format::from_path(path) {
+            eprintln!("Could not export to \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+
+    // solutions that run in-process are built with the profile of this binary.
+    #[cfg(feature = "registry")]
+    if is_release && cfg!(debug_assertions) {
+        eprintln!("--release needs an optimized build with the `registry` feature, as solutions run in the profile of this binary. Use \"cargo time\" or pass --release to cargo run.");
+        process::exit(1);
+    }
+
+    #[cfg(feature = "registry")]
+    let run_solution = |day, is_buffered| in_process::run_solution(day, is_timed, is_buffered);
+
+    #[cfg(not(feature = "registry"))]
+    let run_solution =
+        |day, is_buffered| child_commands::run(day, is_timed, is_release, is_buffered);
+
+    if jobs == 0 {
+        eprintln!("--jobs must be at least 1.");
+        process::exit(1);
+    }
+
+    // benchmarks of concurrent days would compete for the cpu, so timed runs stay serial.
+    let jobs = if is_timed && jobs > 1 {
+        eprintln!("Ignoring --jobs for a timed run, days are benched one after another.");
+        1
+    } else {
+        jobs
+    };
+
+    let mut timings: Vec<Timings> = vec![];
+    let mut statuses: Vec<(Day, DayStatus)> = vec![];
+    let mut cases: Vec<TestCase> = vec![];
+
+    // returns whether the remaining days should still be run.
+    let mut finish = |run: DayRun| {
+        print_status(&run);
+        statuses.push((run.day, run.status));
+        if !reports.is_empty() {
+            cases.extend(test_cases(&run, &answers::load(run.day)));
+        }
+
+        // only days that passed update the benchmarks, the readme keeps the rows of failing ones.
+        if let (DayStatus::Passed, Some(timing)) = (run.status, run.timing) {
+            timings.push(timing);
+        }
+
+        let is_stopped = fail_fast && run.status.is_failure();
+        if is_stopped {
+            println!("\nStopping after day {}, as --fail-fast is set.", run.day);
+        }
+        !is_stopped
+    };
+
+    if jobs == 1 {
+        for day in all_days() {
+            print_header(day);
+            if !finish(run_day(day, false, run_solution)) {
+                break;
+            }
+        }
+    } else {
+        let days: Vec<Day> = all_days().collect();
+        run_parallel(
+            &days,
+            jobs,
+            |day| run_day(day, true, run_solution),
+            |run| {
+                print_header(run.day);
+                print!("{}", run.output);
+                eprint!("{}", run.errors);
+                finish(run)
+            },
+        );
+    }
+
+    println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {}", summarize(&statuses));
+
+    for report in reports {
+        if report.path.is_none() {
+            println!();
+        }
+        match test_report::write(report, &cases, config::get().year) {
+            Ok(()) => {
+                if let Some(path) = &report.path {
+                    println!("Wrote test report to \"{}\".", path.display());
+                }
+            }
+            Err(e) => eprintln!("Failed to write test report: {e}"),
+        }
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
+        if let Some(path) = &export {
+            match benchmark_export::export(path, &timings) {
+                Ok(()) => println!("Exported benchmarks to \"{}\".", path.display()),
+                Err(e) => {
+                    eprintln!("Failed to export benchmarks to \"{}\": {e}", path.display());
+                }
+            }
+        }
+
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+            match readme_benchmarks::update(timings) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -41,6 +383,10 @@ pub fn handle(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    if statuses.iter().any(|(_, status)| status.is_failure()) {
+        process::exit(1);
+    }
 }
 
 #[derive(Debug)]
@@ -56,40 +402,282 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution"),
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// With the `registry` feature, solutions are compiled into the library and run in this process.
+/// The runner prints their results just like the solution binaries do.
+#[cfg(feature = "registry")]
+mod in_process {
+    use super::{DayRun, DayStatus};
+    use crate::template::{
+        config,
+        readme_benchmarks::Timings,
+        registry,
+        runner::{self, PartRun},
+    };
+    use crate::Day;
+    use std::{any::Any, fs, panic, time::Duration};
+
+    /// Runs the solution of a day.
+    /// If `is_buffered` is set, the printed results are returned instead of written to stdout.
+    pub fn run_solution(day: Day, is_timed: bool, is_buffered: bool) -> DayRun {
+        if is_buffered {
+            runner::capture_output();
+        }
+        let mut run = run_captured(day, is_timed);
+        let captured = runner::take_output();
+        run.output = captured.stdout;
+        run.errors = captured.stderr;
+        run
+    }
+
+    fn run_captured(day: Day, is_timed: bool) -> DayRun {
+        let Some(solution) = registry::find(&config::get().bin_name(day)) else {
+            return DayRun::new(day, DayStatus::NotScaffolded);
+        };
+
+        let path = config::get().input_path(day);
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                runner::write_error(&format!(
+                    "Could not open input file \"{}\": {e}\n",
+                    path.display()
+                ));
+                return DayRun::new(day, DayStatus::Failed);
+            }
+        };
+
+        let mut run = DayRun::new(day, DayStatus::Passed);
+
+        // the panic hook already reported the error, the other part and the remaining days are still run.
+        for (result, part) in run.parts.iter_mut().zip(solution.parts) {
+            match panic::catch_unwind(|| part(&input)) {
+                Ok(part_run) => *result = Some(part_run),
+                Err(payload) => {
+                    run.status = DayStatus::Panicked;
+                    run.panic = run.panic.or_else(|| Some(panic_message(payload.as_ref())));
+                }
+            }
+        }
+
+        if let [Some(part_1), Some(part_2)] = &run.parts {
+            run.timing = Some(to_timings(day, part_1, part_2, is_timed));
+        }
+        run
+    }
+
+    fn panic_message(payload: &(dyn Any + Send)) -> String {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| (*s).to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".into())
+    }
+
+    /// Mirrors the timings that are parsed from the output of a solution binary.
+    pub fn to_timings(day: Day, part_1: &PartRun, part_2: &PartRun, is_timed: bool) -> Timings {
+        let timed = |run: &PartRun| (is_timed && run.answer.is_some()).then_some(run.duration);
+        let format = |duration: Duration| format!("{duration:.1?}");
+
+        #[allow(clippy::cast_precision_loss)]
+        let total_nanos = [part_1, part_2]
+            .into_iter()
+            .filter_map(timed)
+            .map(|d| d.as_nanos() as f64)
+            .sum();
+
+        Timings {
+            day,
+            part_1: timed(part_1).map(format),
+            part_2: timed(part_2).map(format),
+            total_nanos,
+        }
+    }
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::to_timings;
+        use crate::day;
+        use crate::template::runner::PartRun;
+        use std::time::Duration;
+
+        fn run(answer: Option<&str>, micros: u64) -> PartRun {
+            PartRun {
+                answer: answer.map(String::from),
+                duration: Duration::from_micros(micros),
+                samples: 100,
+            }
+        }
+
+        #[test]
+        fn mirrors_binary_output() {
+            let res = to_timings(day!(1), &run(Some("42"), 74), &run(None, 10), true);
+            assert_eq!(res.part_1.as_deref(), Some("74.0µs"));
+            assert_eq!(res.part_2, None);
+            assert!((res.total_nanos - 74_000.0).abs() < 1e-6);
+
+            let res = to_timings(day!(1), &run(Some("42"), 74), &run(Some("1"), 10), false);
+            assert_eq!((res.part_1, res.part_2), (None, None));
+        }
+    }
 }
 
-/// All solutions live in isolated binaries.
+/// Without the `registry` feature, solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+#[cfg(not(feature = "registry"))]
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{DayRun, DayStatus, Error, Timings};
+    use crate::template::{
+        answers, config,
+        readme_benchmarks::{get_path_for_bin, parse_duration_nanos},
+        runner::PartRun,
+    };
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Command, ExitStatus, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// What a solution binary printed and how it exited.
+    pub struct Output {
+        pub stdout: Vec<String>,
+        /// Stderr of the binary, only kept if the output is buffered.
+        pub stderr: String,
+        pub status: ExitStatus,
+        /// The message of a panic printed to stderr.
+        pub panic: Option<String>,
+    }
+
+    /// Runs the solution bin of a day and derives its status from the exit code.
+    /// A bin that fails to compile exits with the same code as a panicking one, so the message tells them apart.
+    pub fn run(day: Day, is_timed: bool, is_release: bool, is_buffered: bool) -> DayRun {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return DayRun::new(day, DayStatus::NotScaffolded);
+        }
+
+        let output = match run_solution(day, is_timed, is_release, is_buffered) {
+            Ok(output) => output,
+            Err(e) => {
+                let message = format!("Could not run the solution of day {day}: {e}");
+                let mut run = DayRun::new(day, DayStatus::Failed);
+                if is_buffered {
+                    run.errors = format!("{message}\n");
+                } else {
+                    eprintln!("{message}");
+                }
+                return run;
+            }
+        };
+
+        let status = if output.status.success() {
+            DayStatus::Passed
+        } else if output.panic.is_some() {
+            DayStatus::Panicked
+        } else {
+            DayStatus::Failed
+        };
+
+        // answers are not parsed from stdout, the binary recorded them before printing the next part.
+        let record = answers::load(day);
+        let parts = parse_completed_parts(&output.stdout).map(|part| {
+            part.map(|(number, duration, samples)| PartRun {
+                answer: record.part(number).answer.clone(),
+                duration,
+                samples,
+            })
+        });
+
+        DayRun {
+            day,
+            status,
+            output: if is_buffered {
+                output
+                    .stdout
+                    .iter()
+                    .map(|line| format!("{line}\n"))
+                    .collect()
+            } else {
+                String::new()
+            },
+            errors: output.stderr,
+            timing: Some(parse_exec_time(&output.stdout, day)),
+            parts,
+            panic: output.panic,
+        }
+    }
+
+    /// The parts that ran to completion, with their execution time and sample count.
+    /// The runner ends every part with a line that overwrites its intermediate result after a `\r`.
+    /// Parts that returned [`None`] do not print their execution time.
+    pub fn parse_completed_parts(output: &[String]) -> [Option<(u8, Duration, u128)>; 2] {
+        let mut parts = [None, None];
+
+        for line in output {
+            let Some((_, last)) = line.rsplit_once('\r') else {
+                continue;
+            };
+            let Some(number) = [1, 2]
+                .into_iter()
+                .find(|n| last.starts_with(&format!("Part {n}:")))
+            else {
+                continue;
+            };
+
+            let timing = last
+                .trim_end()
+                .strip_suffix(')')
+                .and_then(|s| s.rsplit_once(" ("))
+                .map(|(_, timing)| timing);
+            let (duration, samples) = match timing.and_then(|t| t.split_once(" @ ")) {
+                Some((duration, samples)) => (
+                    duration,
+                    samples.trim_end_matches(" samples").parse().unwrap_or(1),
+                ),
+                None => (timing.unwrap_or_default(), 1),
+            };
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let duration =
+                Duration::from_nanos(parse_duration_nanos(duration).unwrap_or_default() as u64);
+            parts[usize::from(number - 1)] = Some((number, duration, samples));
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        parts
+    }
+
+    /// Run the solution bin for a given day. Stdout and stderr are only forwarded if the output is not `buffered`.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_buffered: bool,
+    ) -> Result<Output, Error> {
+        let bin_name = config::get().bin_name(day);
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
+        let forwarded = config::forwarded_args();
+        args.push("--");
+        args.extend(forwarded.iter().map(String::as_str));
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
@@ -108,25 +696,50 @@ mod child_commands {
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            let mut panic: Option<String> = None;
+            let mut buffer = String::new();
+            let mut is_message_next = false;
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                if is_buffered {
+                    buffer.push_str(&line);
+                    buffer.push('\n');
+                } else {
+                    eprintln!("{line}");
+                }
+                if is_message_next {
+                    panic = Some(line);
+                    is_message_next = false;
+                } else if let Some((_, rest)) =
+                    line.split_once("panicked at ").filter(|_| panic.is_none())
+                {
+                    // since rust 1.73, the message follows on the next line.
+                    is_message_next = rest.ends_with(':');
+                    panic = Some(rest.to_string());
+                }
             });
+            (panic, buffer)
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
+        for line in stdout.lines().map_while(Result::ok) {
+            if !is_buffered {
+                println!("{line}");
+            }
             output.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let (panic, stderr) = thread.join().map_err(|_| Error::BrokenPipe)?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok(Output {
+            stdout: output,
+            stderr,
+            status,
+            panic,
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
+    pub fn parse_exec_time(output: &[String], day: Day) -> Timings {
+        let mut timings = Timings {
             day,
             part_1: None,
             part_2: None,
@@ -161,27 +774,17 @@ mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_duration_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -202,7 +805,8 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_completed_parts, parse_exec_time};
+        use std::time::Duration;
 
         use crate::day;
 
@@ -250,5 +854,157 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_completed_parts() {
+            let parts = parse_completed_parts(&[
+                "Part 1: 142\rPart 1: 142 (74.1µs @ 100 samples)".into(),
+                "Part 2: ✖\rPart 2: ✖             ".into(),
+            ]);
+            assert_eq!(parts[0], Some((1, Duration::from_nanos(74_100), 100)));
+            assert_eq!(parts[1], Some((2, Duration::ZERO, 1)));
+
+            // a part that panicked during the benchmark never printed its final line.
+            let parts = parse_completed_parts(&[
+                "Part 1: ▼ \rPart 1: ▼  (2.0ms)".into(),
+                "a".into(),
+                "Part 2: 7 > benching".into(),
+            ]);
+            assert_eq!(parts[0], Some((1, Duration::from_millis(2), 1)));
+            assert_eq!(parts[1], None);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_parallel, summarize, test_cases, wrong_parts, DayRun, DayStatus};
+    use crate::template::answers::{DayRecord, PartRecord};
+    use crate::template::runner::PartRun;
+    use crate::template::test_report::Outcome;
+    use crate::{all_days, day, Day};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn reports_in_day_order() {
+        let days: Vec<Day> = all_days().take(6).collect();
+        let mut reported = vec![];
+
+        // earlier days take longer, so they finish last.
+        run_parallel(
+            &days,
+            3,
+            |day| {
+                thread::sleep(Duration::from_millis(u64::from(7 - day.into_inner()) * 5));
+                day
+            },
+            |day| {
+                reported.push(day);
+                true
+            },
+        );
+
+        assert_eq!(reported, days);
+    }
+
+    #[test]
+    fn stops_when_reporting_fails() {
+        let days: Vec<Day> = all_days().collect();
+        let mut reported = vec![];
+
+        run_parallel(
+            &days,
+            2,
+            |day| day,
+            |day| {
+                reported.push(day);
+                day < 3
+            },
+        );
+
+        assert_eq!(reported, days[..3]);
+    }
+
+    #[test]
+    fn detects_wrong_answers() {
+        let part = |answer: Option<&str>, accepted: Option<&str>| PartRecord {
+            answer: answer.map(String::from),
+            accepted: accepted.map(String::from),
+            solved: accepted.is_some(),
+            ..PartRecord::default()
+        };
+
+        let record = DayRecord {
+            part_1: part(Some("35"), Some("35")),
+            part_2: part(Some("47"), Some("46")),
+        };
+        assert_eq!(wrong_parts(&record), [2]);
+
+        let record = DayRecord {
+            part_1: part(None, Some("35")),
+            part_2: part(Some("47"), None),
+        };
+        assert_eq!(wrong_parts(&record), [1]);
+    }
+
+    #[test]
+    fn summarizes_statuses() {
+        let statuses = [
+            (day!(1), DayStatus::Passed),
+            (day!(2), DayStatus::Panicked),
+            (day!(3), DayStatus::Passed),
+            (day!(4), DayStatus::WrongAnswer),
+            (day!(5), DayStatus::NotScaffolded),
+        ];
+
+        assert_eq!(
+            summarize(&statuses),
+            "2 passed, 1 wrong answer, 1 panicked, 1 not scaffolded\nFailed days: 02 (panicked), 04 (wrong answer)"
+        );
+        assert_eq!(summarize(&statuses[..1]), "1 passed");
+    }
+
+    #[test]
+    fn builds_test_cases() {
+        let part_run = |answer: Option<&str>| PartRun {
+            answer: answer.map(String::from),
+            duration: Duration::from_micros(10),
+            samples: 1,
+        };
+        let record = DayRecord {
+            part_1: PartRecord {
+                accepted: Some("35".into()),
+                ..PartRecord::default()
+            },
+            part_2: PartRecord::default(),
+        };
+        let outcomes = |run: &DayRun| test_cases(run, &record).map(|case| case.outcome);
+
+        let mut run = DayRun::new(day!(5), DayStatus::WrongAnswer);
+        run.parts = [Some(part_run(Some("36"))), Some(part_run(None))];
+        assert_eq!(
+            outcomes(&run),
+            [
+                Outcome::Failed("returned 36, expected 35".into()),
+                Outcome::Failed("returned None".into())
+            ]
+        );
+
+        let mut run = DayRun::new(day!(5), DayStatus::Panicked);
+        run.parts = [Some(part_run(Some("35"))), None];
+        run.panic = Some("index out of bounds".into());
+        let cases = test_cases(&run, &record);
+        assert_eq!(cases[0].outcome, Outcome::Passed);
+        assert_eq!(cases[0].duration, Some(Duration::from_micros(10)));
+        assert_eq!(
+            cases[1].outcome,
+            Outcome::Failed("panicked: index out of bounds".into())
+        );
+
+        let run = DayRun::new(day!(6), DayStatus::NotScaffolded);
+        assert_eq!(
+            outcomes(&run),
+            [0, 1].map(|_| Outcome::Skipped("not scaffolded".into()))
+        );
     }
 }
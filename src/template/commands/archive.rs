@@ -0,0 +1,309 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::all_days;
+use crate::template::{
+    config::{self, Layout, CONFIG_FILE},
+    readme_benchmarks::get_marker_for_year,
+    toml_lite::{Document, Value},
+};
+
+/// Moves a workspace with the flat layout into the year layout, namespacing it with the current year.
+pub fn handle() {
+    let config = config::get();
+
+    if config.layout == Layout::Year {
+        eprintln!("The workspace already uses the year layout.");
+        process::exit(1);
+    }
+
+    let Some(year) = config.year else {
+        eprintln!("No year configured. Pass `--year <year>` or set `year` in {CONFIG_FILE}.");
+        process::exit(1);
+    };
+
+    let moves = match plan_moves(&config.bin_dir, &config.data_dir, year) {
+        Ok(moves) => moves,
+        Err(e) => {
+            eprintln!("Failed to read workspace: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Some((_, to)) = moves.iter().find(|(_, to)| to.exists()) {
+        eprintln!("Refusing to overwrite \"{}\".", to.display());
+        process::exit(1);
+    }
+
+    for (from, to) in &moves {
+        if let Err(e) = move_file(from, to) {
+            eprintln!("Failed to move \"{}\": {e}", from.display());
+            process::exit(1);
+        }
+        println!("Moved \"{}\" to \"{}\"", from.display(), to.display());
+    }
+
+    match update_readme(&config.readme, year) {
+        Ok(true) => println!(
+            "Namespaced benchmark markers in \"{}\"",
+            config.readme.display()
+        ),
+        Ok(false) => {}
+        Err(e) => eprintln!("Failed to update benchmark markers in readme: {e}"),
+    }
+
+    match set_year_layout(Path::new(CONFIG_FILE)) {
+        Ok(true) => println!("Set `layout = \"year\"` in {CONFIG_FILE}"),
+        Ok(false) => {}
+        Err(e) => eprintln!("Failed to update {CONFIG_FILE}: {e}"),
+    }
+
+    println!("---");
+    println!("🎄 Archived {} files into {year}.", moves.len());
+}
+
+/// Lists the files to move: solution binaries and every file in the data folders.
+/// Year folders and `.keep` files stay where they are.
+fn plan_moves(
+    bin_dir: &Path,
+    data_dir: &Path,
+    year: u16,
+) -> Result<Vec<(PathBuf, PathBuf)>, io::Error> {
+    let mut moves = vec![];
+
+    for day in all_days() {
+        let from = bin_dir.join(format!("{day}.rs"));
+        if from.exists() {
+            moves.push((from, bin_dir.join(format!("{year}-{day}.rs"))));
+        }
+    }
+
+    if !data_dir.exists() {
+        return Ok(moves);
+    }
+
+    let year_dir = data_dir.join(year.to_string());
+
+    for folder in fs::read_dir(data_dir)? {
+        let folder = folder?;
+        let name = folder.file_name();
+        let name = name.to_string_lossy();
+
+        if !folder.file_type()?.is_dir() || is_year(&name) {
+            continue;
+        }
+
+        collect_files(&folder.path(), &year_dir.join(name.as_ref()), &mut moves)?;
+    }
+
+    Ok(moves)
+}
+
+/// Plans moving the files of `from` into `to`, including nested folders such as per-profile inputs.
+fn collect_files(
+    from: &Path,
+    to: &Path,
+    moves: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<(), io::Error> {
+    for file in fs::read_dir(from)? {
+        let file = file?;
+        let file_type = file.file_type()?;
+
+        if file_type.is_dir() {
+            collect_files(&file.path(), &to.join(file.file_name()), moves)?;
+        } else if file_type.is_file() && file.file_name() != ".keep" {
+            moves.push((file.path(), to.join(file.file_name())));
+        }
+    }
+
+    Ok(())
+}
+
+fn is_year(name: &str) -> bool {
+    name.len() == 4 && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn move_file(from: &Path, to: &Path) -> Result<(), io::Error> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, to)
+}
+
+fn update_readme(path: &Path, year: u16) -> Result<bool, io::Error> {
+    let readme = fs::read_to_string(path)?;
+    let marker = get_marker_for_year(None);
+
+    if !readme.contains(&marker) {
+        return Ok(false);
+    }
+
+    fs::write(
+        path,
+        readme.replace(&marker, &get_marker_for_year(Some(year))),
+    )?;
+    Ok(true)
+}
+
+/// Sets `layout = "year"` in the config file. Returns `false` if it was set already.
+/// Files without a layout get the line on top, which keeps their comments.
+fn set_year_layout(path: &Path) -> Result<bool, io::Error> {
+    let contents = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    let mut doc = Document::parse(&contents)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    match doc.get("", "layout").and_then(Value::as_str) {
+        Some("year") => return Ok(false),
+        Some(_) => {
+            doc.set("", "layout", Value::String("year".into()));
+            fs::write(path, doc.to_string())?;
+        }
+        None => fs::write(path, format!("layout = \"year\"\n{contents}"))?,
+    }
+    Ok(true)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        env, fs,
+        path::{Path, PathBuf},
+        process,
+    };
+
+    use super::{collect_files, plan_moves, set_year_layout, update_readme};
+    use crate::template::readme_benchmarks::get_marker_for_year;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-archive-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn touch(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn plans_moves_into_the_year() {
+        let dir = temp_dir("plan");
+        let (bin_dir, data_dir) = (dir.join("src/bin"), dir.join("data"));
+        for file in [
+            "src/bin/01.rs",
+            "src/bin/2022-01.rs",
+            "src/bin/helpers.rs",
+            "data/inputs/01.txt",
+            "data/inputs/.keep",
+            "data/puzzles/01.md",
+            "data/2022/inputs/01.txt",
+        ] {
+            touch(&dir.join(file));
+        }
+
+        let mut moves = plan_moves(&bin_dir, &data_dir, 2023).unwrap();
+        moves.sort();
+        assert_eq!(
+            moves,
+            [
+                (
+                    data_dir.join("inputs/01.txt"),
+                    data_dir.join("2023/inputs/01.txt")
+                ),
+                (
+                    data_dir.join("puzzles/01.md"),
+                    data_dir.join("2023/puzzles/01.md")
+                ),
+                (bin_dir.join("01.rs"), bin_dir.join("2023-01.rs")),
+            ]
+        );
+
+        assert_eq!(
+            plan_moves(&bin_dir, &dir.join("missing"), 2023)
+                .unwrap()
+                .len(),
+            1
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn collects_nested_files() {
+        let dir = temp_dir("collect");
+        touch(&dir.join("inputs/01.txt"));
+        touch(&dir.join("inputs/work/01.txt"));
+        touch(&dir.join("inputs/work/.keep"));
+
+        let mut moves = vec![];
+        collect_files(&dir.join("inputs"), Path::new("to"), &mut moves).unwrap();
+        moves.sort();
+        assert_eq!(
+            moves,
+            [
+                (dir.join("inputs/01.txt"), PathBuf::from("to/01.txt")),
+                (
+                    dir.join("inputs/work/01.txt"),
+                    PathBuf::from("to/work/01.txt")
+                ),
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn namespaces_readme_markers() {
+        let dir = temp_dir("readme");
+        let path = dir.join("README.md");
+        let (flat, year) = (get_marker_for_year(None), get_marker_for_year(Some(2023)));
+
+        fs::write(&path, format!("# AoC\n{flat}\n| Day |\n{flat}\n")).unwrap();
+        assert!(update_readme(&path, 2023).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("# AoC\n{year}\n| Day |\n{year}\n")
+        );
+        assert!(!update_readme(&path, 2023).unwrap());
+        assert!(update_readme(&dir.join("missing.md"), 2023).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn sets_the_year_layout() {
+        let dir = temp_dir("layout");
+        let path = dir.join("aoc.toml");
+
+        assert!(set_year_layout(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "layout = \"year\"\n");
+        assert!(!set_year_layout(&path).unwrap());
+
+        // similar keys are not a layout.
+        fs::write(
+            &path,
+            "# my workspace\nlayout_old = \"flat\"\nyear = 2023\n",
+        )
+        .unwrap();
+        assert!(set_year_layout(&path).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "layout = \"year\"\n# my workspace\nlayout_old = \"flat\"\nyear = 2023\n"
+        );
+
+        fs::write(&path, "year = 2023\nlayout = \"flat\"\n").unwrap();
+        assert!(set_year_layout(&path).unwrap());
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("layout = \"year\""));
+        assert!(!fs::read_to_string(&path).unwrap().contains("flat"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,349 @@
+use std::{env, fs, path::Path, process, process::Command};
+
+use crate::template::{
+    config::{self, Backend, YearSource, CONFIG_FILE},
+    http::BaseUrl,
+    readme_benchmarks::{self, get_marker, locate_table},
+    session::{self, Source},
+    ANSI_BOLD, ANSI_RESET,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warning,
+    Failed,
+}
+
+struct Check {
+    status: Status,
+    message: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            status: Status::Ok,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn warning(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: Status::Warning,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn failed(message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            status: Status::Failed,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+pub fn handle() {
+    let mut checks = match config::get().client.backend {
+        Backend::AocCli => vec![check_aoc_cli(), check_curl()],
+        Backend::Native => vec![check_base_url()],
+    };
+    checks.extend([
+        check_session(),
+        check_year(),
+        check_data_dirs(),
+        check_readme(),
+    ]);
+
+    for check in &checks {
+        let icon = match check.status {
+            Status::Ok => "✔",
+            Status::Warning => "⚠",
+            Status::Failed => "✖",
+        };
+        println!("{icon} {}", check.message);
+        if let Some(fix) = &check.fix {
+            println!("  → {fix}");
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|c| matches!(c.status, Status::Failed))
+        .count();
+
+    println!("---");
+    if failed == 0 {
+        println!("🎄 {ANSI_BOLD}Everything looks good.{ANSI_RESET}");
+    } else {
+        println!("{ANSI_BOLD}{failed} check(s) failed.{ANSI_RESET}");
+        process::exit(1);
+    }
+}
+
+fn check_aoc_cli() -> Check {
+    match Command::new("aoc").arg("-V").output() {
+        Ok(output) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            Check::ok(format!("{version} is installed."))
+        }
+        Ok(output) => Check::failed(
+            format!("\"aoc -V\" exited with {}.", output.status),
+            "Reinstall aoc-cli with \"cargo install aoc-cli --version 0.12.0 --force\".",
+        ),
+        Err(_) => Check::failed(
+            "command \"aoc\" not found or not callable.",
+            "Install aoc-cli with \"cargo install aoc-cli --version 0.12.0\" and make sure \"~/.cargo/bin\" is on your PATH.",
+        ),
+    }
+}
+
+/// `curl` fetches private leaderboards for the aoc-cli backend.
+fn check_curl() -> Check {
+    match Command::new("curl").arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            // e.g. "curl 7.88.1 (x86_64-pc-linux-gnu) libcurl/7.88.1 ..."
+            let version = stdout
+                .split_whitespace()
+                .take(2)
+                .collect::<Vec<_>>()
+                .join(" ");
+            Check::ok(format!("{version} is installed."))
+        }
+        _ => Check::warning(
+            "command \"curl\" not found or not callable, \"cargo leaderboard\" will not work.",
+            format!("Install curl or set `client.backend = \"native\"` in {CONFIG_FILE}."),
+        ),
+    }
+}
+
+/// The native client only talks to plain `http://` urls.
+fn check_base_url() -> Check {
+    let base_url = &config::get().client.base_url;
+    match BaseUrl::parse(base_url) {
+        Ok(_) => Check::ok(format!("The native client uses \"{base_url}\".")),
+        Err(e) => Check::failed(
+            format!("`client.base_url` is not usable: {e}"),
+            format!("Change `client.base_url` or `client.backend` in {CONFIG_FILE}."),
+        ),
+    }
+}
+
+fn check_session() -> Check {
+    if let Some((_, Source::Env)) = session::read_session_with_source() {
+        return Check::ok(format!(
+            "Session cookie set by the {} environment variable.",
+            session::SESSION_ENV
+        ));
+    }
+
+    if let Some(profile) = session::active_profile() {
+        return match session::read_session_with_source() {
+            Some(_) => Check::ok(format!("Session cookie of profile \"{profile}\" found.")),
+            None => Check::failed(
+                format!("Profile \"{profile}\" has no session cookie."),
+                format!("Run \"cargo session set <cookie> --profile {profile}\"."),
+            ),
+        };
+    }
+
+    let candidates = session::session_file_candidates();
+
+    if candidates.is_empty() {
+        return Check::failed(
+            "Could not determine your home directory.",
+            "Set the HOME environment variable.",
+        );
+    }
+
+    match session::find_session_file() {
+        Some(path) => match fs::read_to_string(&path) {
+            Ok(s) if !s.trim().is_empty() => {
+                Check::ok(format!("Session cookie found in \"{}\".", path.display()))
+            }
+            _ => Check::failed(
+                format!("Session cookie file \"{}\" is empty.", path.display()),
+                "Run \"cargo session set <cookie>\" with the `session` cookie from adventofcode.com.",
+            ),
+        },
+        None => Check::failed(
+            "No session cookie file found.",
+            format!(
+                "Run \"cargo session set <cookie>\" to create \"{}\".",
+                candidates[0].display()
+            ),
+        ),
+    }
+}
+
+fn check_year() -> Check {
+    let config = config::get();
+    check_year_setting(config.year, config.year_source, env::var("AOC_YEAR").ok())
+}
+
+/// Checks the year the config resolved, see [`config::get`] for where it is taken from.
+/// `env_year` is only used to explain why no year was found.
+fn check_year_setting(
+    year: Option<u16>,
+    source: Option<YearSource>,
+    env_year: Option<String>,
+) -> Check {
+    let source = source.map_or_else(|| "the config".into(), |s| s.to_string());
+    match (year, env_year) {
+        (Some(year), _) if year >= 2015 => Check::ok(format!("Year is set to {year} by {source}.")),
+        (Some(year), _) => Check::failed(
+            format!("Year is set to {year} by {source}, which is not a valid year."),
+            format!("Change {source} to a year like \"2023\"."),
+        ),
+        (None, Some(env_year)) => Check::failed(
+            format!("AOC_YEAR is set to \"{env_year}\", which is not a valid year."),
+            "Set `AOC_YEAR` in the `[env]` section of \".cargo/config.toml\" to a year like \"2023\".",
+        ),
+        (None, None) => Check::failed(
+            "No year is set.",
+            format!("Run commands through cargo (e.g. \"cargo doctor\") or set `year` in {CONFIG_FILE}."),
+        ),
+    }
+}
+
+fn check_data_dirs() -> Check {
+    check_data_dirs_in(&config::get().year_data_dir())
+}
+
+fn check_data_dirs_in(data_dir: &Path) -> Check {
+    let missing: Vec<String> = ["inputs", "examples", "puzzles"]
+        .iter()
+        .map(|folder| data_dir.join(folder))
+        .filter(|path| !path.is_dir())
+        .map(|path| path.display().to_string())
+        .collect();
+
+    if missing.is_empty() {
+        Check::ok(format!(
+            "Data directories exist in \"{}\".",
+            data_dir.display()
+        ))
+    } else {
+        Check::failed(
+            format!("Missing data directories: {}.", missing.join(", ")),
+            format!("Create them with \"mkdir -p {}\".", missing.join(" ")),
+        )
+    }
+}
+
+fn check_readme() -> Check {
+    let path = &config::get().readme;
+    let marker = get_marker();
+
+    let readme = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) => {
+            return Check::failed(
+                format!("Could not read \"{}\": {e}.", path.display()),
+                format!("Create the readme or set `paths.readme` in {CONFIG_FILE}."),
+            )
+        }
+    };
+
+    check_readme_markers(path, &readme, &marker)
+}
+
+fn check_readme_markers(path: &Path, readme: &str, marker: &str) -> Check {
+    match locate_table(readme, marker) {
+        // a single marker is replaced by a full table on the first benchmark run.
+        Ok(pos) if pos.pos_end - pos.pos_start == marker.len() => Check::warning(
+            format!("\"{}\" contains a single benchmark marker.", path.display()),
+            "Run \"cargo time\" to generate the table, which adds the closing marker.",
+        ),
+        Ok(_) => Check::ok(format!(
+            "\"{}\" contains both benchmark markers.",
+            path.display()
+        )),
+        Err(readme_benchmarks::Error::Parser(e)) => Check::failed(
+            format!(
+                "Benchmark markers in \"{}\" are invalid: {e}",
+                path.display()
+            ),
+            format!("Make sure \"{marker}\" appears exactly twice around the benchmark table."),
+        ),
+        Err(readme_benchmarks::Error::IO(e)) => Check::failed(
+            format!("Could not read \"{}\": {e}.", path.display()),
+            format!("Set `paths.readme` in {CONFIG_FILE}."),
+        ),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, path::Path, process};
+
+    use super::{check_data_dirs_in, check_readme_markers, check_year_setting, Status};
+    use crate::template::config::YearSource;
+    use crate::template::readme_benchmarks::get_marker;
+
+    #[test]
+    fn checks_the_year() {
+        let check = check_year_setting(Some(2023), Some(YearSource::ConfigFile), None);
+        assert_eq!(check.status, Status::Ok);
+        assert_eq!(check.message, "Year is set to 2023 by `year` in aoc.toml.");
+
+        // the config file takes precedence over a stray AOC_YEAR.
+        let check = check_year_setting(Some(2023), Some(YearSource::ConfigFile), Some("23".into()));
+        assert_eq!(check.status, Status::Ok);
+
+        let check = check_year_setting(Some(23), Some(YearSource::Argument), None);
+        assert_eq!(check.status, Status::Failed);
+        assert!(check.message.contains("the --year argument"));
+
+        let check = check_year_setting(None, None, Some("next".into()));
+        assert_eq!(check.status, Status::Failed);
+        assert!(check.message.contains("\"next\""));
+        assert_eq!(check_year_setting(None, None, None).status, Status::Failed);
+    }
+
+    #[test]
+    fn warns_about_a_single_marker() {
+        let path = Path::new("README.md");
+        let marker = get_marker();
+
+        let single = format!("# Advent of Code\n{marker}\n");
+        let check = check_readme_markers(path, &single, &marker);
+        assert_eq!(check.status, Status::Warning);
+        assert!(check.message.contains("single benchmark marker"));
+
+        let both = format!("# Advent of Code\n{marker}\n| Day |\n{marker}\n");
+        assert_eq!(
+            check_readme_markers(path, &both, &marker).status,
+            Status::Ok
+        );
+
+        let three = format!("{marker}\n{marker}\n{marker}\n");
+        assert_eq!(
+            check_readme_markers(path, &three, &marker).status,
+            Status::Failed
+        );
+    }
+
+    #[test]
+    fn reports_missing_data_dirs() {
+        let data_dir = env::temp_dir().join(format!("aoc-doctor-{}", process::id()));
+        fs::create_dir_all(data_dir.join("inputs")).unwrap();
+
+        let check = check_data_dirs_in(&data_dir);
+        assert_eq!(check.status, Status::Failed);
+        assert!(!check.message.contains("inputs"));
+        assert!(check.message.contains("examples"));
+        assert!(check.message.contains("puzzles"));
+
+        fs::create_dir_all(data_dir.join("examples")).unwrap();
+        fs::create_dir_all(data_dir.join("puzzles")).unwrap();
+        assert_eq!(check_data_dirs_in(&data_dir).status, Status::Ok);
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+}
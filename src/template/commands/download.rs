@@ -1,15 +1,19 @@
-use crate::template::aoc_cli;
+use crate::template::aoc_client;
 use crate::Day;
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(day: Day, force: bool) {
+    let client = aoc_client::get();
+
+    if let Err(e) = client.check() {
+        eprintln!("{e}");
+        eprintln!("Run \"cargo doctor\" to check your setup.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = client.download(day, force) {
+        eprintln!("{e}");
+        eprintln!("Run \"cargo doctor\" to check your setup.");
         process::exit(1);
     };
 }
@@ -0,0 +1,208 @@
+use std::{fs, path::Path, process};
+
+use crate::template::puzzle::{self, CodeBlock};
+use crate::template::{config, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// Blocks with fewer lines are usually inline snippets rather than the example input.
+const MIN_EXAMPLE_LINES: usize = 2;
+
+pub fn handle(day: Day, list: bool, part_one: Option<usize>, part_two: Option<usize>, force: bool) {
+    let Some(markdown) = puzzle::read(day) else {
+        eprintln!(
+            "Could not read \"{}\". Run \"cargo download {day}\" first.",
+            config::get().puzzle_path(day).display()
+        );
+        process::exit(1);
+    };
+
+    let blocks = puzzle::code_blocks(&markdown);
+    let answers = puzzle::expected_answers(&markdown);
+
+    print_blocks(&blocks);
+    for (i, answer) in answers.iter().enumerate() {
+        if let Some(answer) = answer {
+            println!(
+                "Expected answer of part {}: {ANSI_BOLD}{answer}{ANSI_RESET}",
+                i + 1
+            );
+        }
+    }
+
+    if list {
+        return;
+    }
+
+    let pick = |part: u8, choice: Option<usize>| match choice {
+        Some(index) => match blocks.get(index.wrapping_sub(1)) {
+            Some(block) => Some(block),
+            None => {
+                eprintln!("There is no code block #{index}.");
+                process::exit(1);
+            }
+        },
+        None => detect_example(&blocks, part),
+    };
+
+    let Some(example_one) = pick(1, part_one) else {
+        eprintln!("Could not detect an example, pick one with --part-one <block>.");
+        process::exit(1);
+    };
+    let example_two = pick(2, part_two).filter(|b| b.content != example_one.content);
+
+    let config = config::get();
+    let written = match example_two {
+        None => write_example(&config.example_path(day), &example_one.content, force),
+        Some(example_two) => {
+            let path = |part: u8| config.data_path("examples", &format!("{day}-{part}.txt"));
+            write_example(&path(1), &example_one.content, force)
+                && write_example(&path(2), &example_two.content, force)
+        }
+    };
+
+    if written {
+        fill_solution_tests(day, &answers, example_two.is_some());
+    }
+}
+
+fn print_blocks(blocks: &[CodeBlock]) {
+    for (i, block) in blocks.iter().enumerate() {
+        let preview = block.content.lines().next().unwrap_or_default();
+        let preview: String = preview.chars().take(40).collect();
+        println!(
+            "#{:<3} part {} {:>4} lines  {ANSI_ITALIC}{preview}{ANSI_RESET}",
+            i + 1,
+            block.part,
+            block.line_count()
+        );
+    }
+}
+
+/// The first multi-line block in the section of a part.
+fn detect_example(blocks: &[CodeBlock], part: u8) -> Option<&CodeBlock> {
+    blocks
+        .iter()
+        .find(|b| b.part == part && b.line_count() >= MIN_EXAMPLE_LINES)
+}
+
+/// Writes an example file. Files with content are only replaced with `--force`.
+fn write_example(path: &Path, content: &str, force: bool) -> bool {
+    let has_content = fs::metadata(path).is_ok_and(|m| m.len() > 0);
+    if has_content && !force {
+        eprintln!(
+            "\"{}\" already has content, pass --force to replace it.",
+            path.display()
+        );
+        return false;
+    }
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, content));
+
+    match result {
+        Ok(()) => {
+            println!("Created example file \"{}\"", path.display());
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write example file \"{}\": {e}", path.display());
+            false
+        }
+    }
+}
+
+/// Fills in the expected answers of the tests created by `scaffold`, leaving edited tests alone.
+fn fill_solution_tests(day: Day, answers: &[Option<String>; 2], split: bool) {
+    let path = config::get().bin_path(day);
+    let Ok(source) = fs::read_to_string(&path) else {
+        return;
+    };
+
+    let updated = fill_tests(&source, answers, split);
+    if updated == source {
+        return;
+    }
+
+    match fs::write(&path, updated) {
+        Ok(()) => println!("Updated tests in \"{}\"", path.display()),
+        Err(e) => eprintln!("Failed to update tests in \"{}\": {e}", path.display()),
+    }
+}
+
+fn fill_tests(source: &str, answers: &[Option<String>; 2], split: bool) -> String {
+    let mut source = source.to_string();
+
+    for (i, test_name) in ["fn test_part_one()", "fn test_part_two()"]
+        .iter()
+        .enumerate()
+    {
+        let part = i + 1;
+        let Some(start) = source.find(test_name) else {
+            continue;
+        };
+        let end = source[start..]
+            .find("\n    }\n")
+            .map_or(source.len(), |e| start + e);
+        let mut body = source[start..end].to_string();
+
+        if split {
+            body = body.replace(
+                "read_file(\"examples\", DAY)",
+                &format!("read_file_part(\"examples\", DAY, {part})"),
+            );
+        }
+
+        // only numeric answers fit the scaffolded return type.
+        let answer = answers[i].as_ref().filter(|a| a.parse::<i64>().is_ok());
+        if let Some(answer) = answer {
+            body = body.replace(
+                "assert_eq!(result, None);",
+                &format!("assert_eq!(result, Some({answer}));"),
+            );
+        }
+
+        source.replace_range(start..end, &body);
+    }
+
+    source
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::fill_tests;
+
+    const SOURCE: &str = r#"#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(7));
+    }
+}
+"#;
+
+    #[test]
+    fn fills_scaffolded_tests() {
+        let answers = [Some("142".to_string()), Some("281".to_string())];
+        let filled = fill_tests(SOURCE, &answers, true);
+
+        assert!(filled.contains(
+            "read_file_part(\"examples\", DAY, 1));\n        assert_eq!(result, Some(142));"
+        ));
+        // edited assertions are kept.
+        assert!(filled.contains(
+            "read_file_part(\"examples\", DAY, 2));\n        assert_eq!(result, Some(7));"
+        ));
+        assert_eq!(fill_tests(SOURCE, &[None, None], false), SOURCE);
+    }
+}
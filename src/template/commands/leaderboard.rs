@@ -0,0 +1,87 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::leaderboard::Leaderboard;
+use crate::template::{aoc_client, config};
+
+/// The website asks to not request a leaderboard more often than every 15 minutes.
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+fn get_cache_path(id: u64) -> PathBuf {
+    config::get().data_path("leaderboards", &format!("{id}.json"))
+}
+
+fn cache_age(path: &Path) -> Option<Duration> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+/// Shows a private leaderboard. A cached copy is used while it is younger than 15 minutes,
+/// or always with `offline`. `refresh` fetches a new copy regardless of the cache.
+pub fn handle(id: u64, refresh: bool, offline: bool) {
+    let cache_path = get_cache_path(id);
+    let is_fresh = cache_age(&cache_path).is_some_and(|age| age < CACHE_TTL);
+
+    let leaderboard = if offline || (is_fresh && !refresh) {
+        match fs::read_to_string(&cache_path) {
+            Ok(json) => parse(&json, id),
+            Err(e) => {
+                eprintln!(
+                    "Could not read cached leaderboard \"{}\": {e}",
+                    cache_path.display()
+                );
+                process::exit(1);
+            }
+        }
+    } else {
+        fetch(id, &cache_path)
+    };
+
+    print!("{}", leaderboard.render());
+}
+
+fn parse(json: &str, id: u64) -> Leaderboard {
+    match Leaderboard::parse(json) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Could not parse leaderboard: {e}");
+            eprintln!("The session cookie might not have access to leaderboard {id}.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Fetches a leaderboard and caches it. Responses that are not a leaderboard, e.g. the login page
+/// served for an expired session, are never cached.
+fn fetch(id: u64, cache_path: &Path) -> Leaderboard {
+    let client = aoc_client::get();
+
+    let result = client.check().and_then(|()| client.fetch_leaderboard(id));
+    let json = match result {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("Run \"cargo doctor\" to check your setup.");
+            process::exit(1);
+        }
+    };
+
+    let leaderboard = parse(&json, id);
+
+    let written = cache_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(cache_path, &json));
+    if let Err(e) = written {
+        eprintln!(
+            "Could not cache leaderboard in \"{}\": {e}",
+            cache_path.display()
+        );
+    }
+
+    leaderboard
+}
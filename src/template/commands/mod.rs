@@ -1,5 +1,12 @@
 pub mod all;
+pub mod archive;
+pub mod doctor;
 pub mod download;
+pub mod examples;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
+pub mod session;
 pub mod solve;
+pub mod stars;
+pub mod status;
@@ -1,16 +1,76 @@
-use std::process;
+use std::{
+    env,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::aoc_cli;
+use crate::template::{aoc_client, config, puzzle};
 use crate::Day;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+const DEFAULT_WIDTH: usize = 80;
+
+/// The width of the terminal, capped for readability.
+/// Shells rarely export `COLUMNS`, so `tput cols` is asked next. It finds the terminal through stderr.
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.trim().parse().ok())
+        .or_else(tput_cols)
+        .unwrap_or(DEFAULT_WIDTH)
+        .clamp(20, 100)
+}
+
+fn tput_cols() -> Option<usize> {
+    let output = Command::new("tput")
+        .arg("cols")
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Renders the downloaded puzzle description. It is only fetched if missing or with `refresh`.
+pub fn handle(day: Day, part: Option<u8>, refresh: bool) {
+    if refresh || puzzle::read(day).is_none() {
+        let client = aoc_client::get();
+
+        if let Err(e) = client.check().and_then(|()| client.refresh_puzzle(day)) {
+            eprintln!("{e}");
+            eprintln!("Run \"cargo doctor\" to check your setup.");
+            process::exit(1);
+        }
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let Some(markdown) = puzzle::read(day) else {
+        eprintln!(
+            "Could not read \"{}\".",
+            config::get().puzzle_path(day).display()
+        );
         process::exit(1);
     };
+
+    let markdown = match part {
+        None => markdown,
+        Some(part @ (1 | 2)) => {
+            let [part_one, part_two] = puzzle::sections(&markdown);
+            match part {
+                1 => part_one,
+                _ if part_two.is_empty() => {
+                    eprintln!("Part two is not unlocked yet. Once part one is solved, run \"cargo read {day} --refresh\".");
+                    process::exit(1);
+                }
+                _ => part_two,
+            }
+        }
+        Some(part) => {
+            eprintln!("Unexpected part {part}, expected 1 or 2.");
+            process::exit(1);
+        }
+    };
+
+    print!("{}", puzzle::render(&markdown, terminal_width()));
 }
@@ -1,9 +1,11 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
+use crate::template::config::{self, Config};
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -34,18 +36,45 @@ mod tests {
 }
 "#;
 
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    create_parent_dir(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// The files created for a day: the solution module, the input and the example.
+/// Modules are found by cargo's target auto-discovery, e.g. `src/bin/2023-05.rs` as binary `2023-05`,
+/// so `Cargo.toml` needs no `[[bin]]` entry.
+fn scaffold_paths(config: &Config, day: Day) -> [String; 3] {
+    [
+        config.bin_path(day),
+        config.input_path(day),
+        config.example_path(day),
+    ]
+    .map(|path| path.display().to_string())
+}
+
+fn module_contents(day: Day) -> String {
+    MODULE_TEMPLATE.replace("DAY_NUMBER", &day.into_inner().to_string())
 }
 
 pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let [module_path, input_path, example_path] = scaffold_paths(config::get(), day);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -55,11 +84,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module_contents(day).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -92,3 +117,56 @@ pub fn handle(day: Day) {
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, io::Write, path::Path, process};
+
+    use super::{create_file, module_contents, safe_create_file, scaffold_paths};
+    use crate::day;
+    use crate::template::config::{Config, Layout};
+    use crate::template::toml_lite::{Document, Value};
+
+    #[test]
+    fn scaffolds_the_year_layout() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut config = Config::default();
+        config.layout = Layout::Year;
+        config.year = Some(2023);
+        config.bin_dir = dir.join("src/bin");
+        config.data_dir = dir.join("data");
+
+        let [module_path, input_path, example_path] = scaffold_paths(&config, day!(5));
+        assert_eq!(Path::new(&module_path), dir.join("src/bin/2023-05.rs"));
+        assert_eq!(Path::new(&input_path), dir.join("data/2023/inputs/05.txt"));
+        assert_eq!(
+            Path::new(&example_path),
+            dir.join("data/2023/examples/05.txt")
+        );
+
+        let mut file = safe_create_file(&module_path).unwrap();
+        file.write_all(module_contents(day!(5)).as_bytes()).unwrap();
+        create_file(&input_path).unwrap();
+        create_file(&example_path).unwrap();
+
+        let module = fs::read_to_string(&module_path).unwrap();
+        assert!(module.starts_with("advent_of_code::solution!(5);"));
+        assert!(Path::new(&input_path).is_file());
+        assert!(Path::new(&example_path).is_file());
+        // an existing solution is never overwritten.
+        assert!(safe_create_file(&module_path).is_err());
+
+        // the binary `2023-05` is only built if cargo discovers `src/bin` on its own.
+        let manifest =
+            fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml")).unwrap();
+        let manifest = Document::parse(&manifest).unwrap();
+        assert_ne!(
+            manifest.get("package", "autobins").and_then(Value::as_bool),
+            Some(false)
+        );
+        assert!(manifest.table("bin").is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
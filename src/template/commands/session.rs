@@ -0,0 +1,136 @@
+use std::{fs, process};
+
+use crate::template::session::{self, Source};
+use crate::template::{aoc_client, ANSI_BOLD, ANSI_RESET};
+
+pub enum SessionCommand {
+    /// Stores a cookie for the default session file or a named profile.
+    Set {
+        cookie: String,
+        profile: Option<String>,
+    },
+    Show,
+    Validate,
+    List,
+    /// Activates a profile, `default` switches back to the default session file.
+    Switch {
+        profile: String,
+    },
+}
+
+pub fn handle(command: SessionCommand) {
+    match command {
+        SessionCommand::Set { cookie, profile } => set(&cookie, profile.as_deref()),
+        SessionCommand::Show => show(),
+        SessionCommand::Validate => validate(),
+        SessionCommand::List => list(),
+        SessionCommand::Switch { profile } => switch(&profile),
+    }
+}
+
+fn check_profile_name(name: &str) {
+    if !session::is_valid_profile_name(name) {
+        eprintln!(
+            "Invalid profile name \"{name}\". Use letters, digits, `-` and `_`, other than \"default\"."
+        );
+        process::exit(1);
+    }
+}
+
+fn set(cookie: &str, profile: Option<&str>) {
+    if let Some(name) = profile {
+        check_profile_name(name);
+    }
+
+    let cookie = cookie.trim().trim_start_matches("session=");
+    if cookie.is_empty() {
+        eprintln!("The session cookie must not be empty.");
+        process::exit(1);
+    }
+
+    let Some(path) = session::writable_session_file(profile) else {
+        eprintln!("Could not determine your home directory. Set the HOME environment variable.");
+        process::exit(1);
+    };
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| session::write_private(&path, cookie));
+
+    match result {
+        Ok(()) => println!(
+            "Stored session {} in \"{}\"",
+            session::mask(cookie),
+            path.display()
+        ),
+        Err(e) => {
+            eprintln!("Failed to write \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+fn show() {
+    let profile = session::active_profile();
+    println!("Profile: {}", profile.as_deref().unwrap_or("default"));
+
+    match session::read_session_with_source() {
+        Some((cookie, source)) => {
+            let source = match source {
+                Source::Env => format!("the {} environment variable", session::SESSION_ENV),
+                Source::File(path) => format!("\"{}\"", path.display()),
+            };
+            println!("Session: {} (from {source})", session::mask(&cookie));
+        }
+        None => {
+            eprintln!("No session cookie found. Set one with \"cargo session set <cookie>\".");
+            process::exit(1);
+        }
+    }
+}
+
+fn validate() {
+    let client = aoc_client::get();
+
+    let result = client.check().and_then(|()| client.validate_session());
+
+    match result {
+        Ok(()) => println!("{ANSI_BOLD}The session cookie is valid.{ANSI_RESET}"),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn list() {
+    let active = session::active_profile();
+    let marker = |is_active: bool| if is_active { "*" } else { " " };
+
+    println!("{} default", marker(active.is_none()));
+    for name in session::profiles() {
+        println!("{} {name}", marker(active.as_ref() == Some(&name)));
+    }
+}
+
+fn switch(profile: &str) {
+    let name = (profile != "default").then_some(profile);
+
+    if let Some(name) = name {
+        check_profile_name(name);
+        if !session::profile_file(name).is_some_and(|p| p.exists()) {
+            eprintln!(
+                "Unknown profile \"{name}\". Create it with \"cargo session set <cookie> --profile {name}\"."
+            );
+            process::exit(1);
+        }
+    }
+
+    if let Err(e) = session::set_active_profile(name) {
+        eprintln!("Failed to switch profile: {e}");
+        process::exit(1);
+    }
+
+    println!("Switched to profile \"{profile}\".");
+}
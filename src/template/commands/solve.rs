@@ -1,9 +1,14 @@
 use std::process::{Command, Stdio};
 
+use crate::template::config;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(day: Day, release: bool, time: bool, profile: bool, submit_part: Option<u8>) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        config::get().bin_name(day),
+    ];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -20,6 +25,12 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if profile {
+        cmd_args.push("--profile".to_string());
+    }
+
+    cmd_args.extend(config::forwarded_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -0,0 +1,26 @@
+use std::process;
+
+use crate::template::{config, readme_stars};
+
+/// Fills the `advent_readme_stars` table of the readme with the stars recorded by `solve --submit`.
+pub fn handle() {
+    let Some(year) = config::get().year else {
+        eprintln!("The puzzle links need a year, please set AOC_YEAR.");
+        process::exit(1);
+    };
+
+    let rows = readme_stars::collect_rows();
+    let stars: usize = rows.iter().flat_map(|r| r.stars).filter(|s| *s).count();
+
+    match readme_stars::update(year, &rows) {
+        Ok(()) => println!("Successfully updated README with {stars} stars."),
+        Err(_) => {
+            eprintln!("Failed to update readme with stars.");
+            eprintln!(
+                "Make sure the readme contains the \"{}\" marker.",
+                readme_stars::get_marker()
+            );
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+use crate::template::{
+    answers::{self, DayRecord},
+    config,
+    readme_benchmarks::get_path_for_bin,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{all_days, Day};
+
+const CELL_WIDTH: usize = 7;
+
+/// What is present in the workspace for a single day.
+struct DayProgress {
+    day: Day,
+    has_bin: bool,
+    has_input: bool,
+    has_example: bool,
+    record: DayRecord,
+}
+
+impl DayProgress {
+    /// Parts the website accepted an answer for. Answers that were never submitted earn no star.
+    fn stars(&self) -> usize {
+        usize::from(self.record.part_1.solved) + usize::from(self.record.part_2.solved)
+    }
+}
+
+pub fn handle(run: bool) {
+    let progress: Vec<DayProgress> = all_days()
+        .map(|day| {
+            let has_bin = Path::new(&get_path_for_bin(day)).exists();
+
+            if run && has_bin {
+                quick_run(day);
+            }
+
+            scan_day(day, has_bin)
+        })
+        .collect();
+
+    let year = config::get()
+        .year
+        .map_or_else(String::new, |y| format!(" {y}"));
+    println!("{ANSI_BOLD}🎄 Advent of Code{year}{ANSI_RESET}");
+    println!();
+    println!("{}", render_calendar(&progress, config::get().year));
+    println!("{}", render_details(&progress));
+
+    let stars: usize = progress.iter().map(DayProgress::stars).sum();
+    println!();
+    println!("{ANSI_BOLD}{stars}{ANSI_RESET}/50 ⭐ collected.");
+}
+
+fn scan_day(day: Day, has_bin: bool) -> DayProgress {
+    let config = config::get();
+
+    DayProgress {
+        day,
+        has_bin,
+        has_input: is_non_empty(&config.input_path(day)),
+        has_example: is_non_empty(&config.example_path(day))
+            || is_non_empty(&config.data_path("examples", &format!("{day}-1.txt"))),
+        record: answers::load(day),
+    }
+}
+
+fn is_non_empty(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+/// Runs the solution once so that the runner refreshes its answer record.
+fn quick_run(day: Day) {
+    println!("{ANSI_ITALIC}Running day {day}...{ANSI_RESET}");
+
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &config::get().bin_name(day),
+        ])
+        .arg("--")
+        .args(config::forwarded_args())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+
+    if !status.is_ok_and(|s| s.success()) {
+        eprintln!("Day {day} did not run successfully.");
+    }
+}
+
+/// Weekday of December 1st, with Monday being `0`.
+fn first_weekday(year: u16) -> usize {
+    // Sakamoto's method, shifted so that the week starts on Monday.
+    const OFFSETS: [usize; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+    let y = usize::from(year);
+    let sunday_based = (y + y / 4 - y / 100 + y / 400 + OFFSETS[11] + 1) % 7;
+    (sunday_based + 6) % 7
+}
+
+fn render_cell(progress: &DayProgress) -> String {
+    let stars = match progress.stars() {
+        2 => "★★",
+        1 => "★☆",
+        _ if progress.has_bin => "☆☆",
+        _ => "  ",
+    };
+
+    let cell = format!("{} {stars}", progress.day);
+    let padding = " ".repeat(CELL_WIDTH - cell.chars().count());
+
+    if progress.stars() == 2 {
+        format!("{ANSI_BOLD}{cell}{ANSI_RESET}{padding}")
+    } else {
+        format!("{cell}{padding}")
+    }
+}
+
+fn render_calendar(progress: &[DayProgress], year: Option<u16>) -> String {
+    let offset = year.map_or(0, first_weekday);
+    let mut lines = vec![];
+
+    if year.is_some() {
+        lines.push(
+            ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+                .map(|d| format!("{d:<CELL_WIDTH$}"))
+                .join(" ")
+                .trim_end()
+                .to_string(),
+        );
+    }
+
+    let mut cells: Vec<String> = vec![" ".repeat(CELL_WIDTH); offset];
+    cells.extend(progress.iter().map(render_cell));
+
+    let columns = if year.is_some() { 7 } else { 5 };
+    for row in cells.chunks(columns) {
+        lines.push(row.join(" ").trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+fn format_timing(timing: Option<(Duration, u128)>) -> String {
+    timing.map_or_else(|| "-".into(), |(duration, _)| format!("{duration:.1?}"))
+}
+
+fn render_details(progress: &[DayProgress]) -> String {
+    let check = |b: bool| if b { "✔" } else { "✖" };
+
+    let mut lines = vec![
+        String::new(),
+        format!(
+            "{ANSI_BOLD}{:<4} {:<4} {:<6} {:<8} {:<7} {:<7} {:<12} {}{ANSI_RESET}",
+            "Day", "Bin", "Input", "Example", "Part 1", "Part 2", "Time 1", "Time 2"
+        ),
+    ];
+
+    for p in progress.iter().filter(|p| p.has_bin || p.has_input) {
+        lines.push(format!(
+            "{:<4} {:<4} {:<6} {:<8} {:<7} {:<7} {:<12} {}",
+            p.day.to_string(),
+            check(p.has_bin),
+            check(p.has_input),
+            check(p.has_example),
+            check(p.record.part_1.answer.is_some()),
+            check(p.record.part_2.answer.is_some()),
+            format_timing(p.record.part_1.timing),
+            format_timing(p.record.part_2.timing),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{first_weekday, render_calendar, DayProgress};
+    use crate::{
+        all_days, day,
+        template::answers::{DayRecord, PartRecord},
+    };
+
+    #[test]
+    fn computes_first_weekday_of_december() {
+        // Dec 1st 2023 was a Friday, 2022 a Thursday, 2015 a Tuesday.
+        assert_eq!(first_weekday(2023), 4);
+        assert_eq!(first_weekday(2022), 3);
+        assert_eq!(first_weekday(2015), 1);
+    }
+
+    #[test]
+    fn counts_solved_parts_as_stars() {
+        let progress = DayProgress {
+            day: day!(1),
+            has_bin: true,
+            has_input: true,
+            has_example: true,
+            record: DayRecord {
+                part_1: PartRecord {
+                    answer: Some("142".into()),
+                    solved: true,
+                    ..PartRecord::default()
+                },
+                part_2: PartRecord {
+                    answer: Some("280".into()),
+                    ..PartRecord::default()
+                },
+            },
+        };
+
+        assert_eq!(progress.stars(), 1);
+    }
+
+    #[test]
+    fn renders_calendar_rows() {
+        let progress: Vec<DayProgress> = all_days()
+            .map(|day| DayProgress {
+                day,
+                has_bin: false,
+                has_input: false,
+                has_example: false,
+                record: DayRecord::default(),
+            })
+            .collect();
+
+        let calendar = render_calendar(&progress, Some(2023));
+        let lines: Vec<&str> = calendar.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("Mon"));
+        assert_eq!(lines[1].trim(), "01      02      03");
+        assert!(lines[5].starts_with("25"));
+    }
+}
@@ -0,0 +1,697 @@
+//! Workspace configuration, read from an optional `aoc.toml` in the workspace root.
+//! Every setting falls back to the template's defaults when it is not present.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::template::session;
+use crate::template::toml_lite::{self, Document, Table, Value};
+use crate::Day;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(toml_lite::ParseError),
+    Invalid(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<toml_lite::ParseError> for Error {
+    fn from(e: toml_lite::ParseError) -> Self {
+        Error::Parser(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{CONFIG_FILE}: {e}"),
+            Error::Invalid(msg) => write!(f, "{CONFIG_FILE}: {msg}"),
+            Error::IO(e) => write!(f, "could not read {CONFIG_FILE}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// How solutions are benched when running with `--time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchSettings {
+    /// Approximate total execution time to spend on benching a part.
+    pub target: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchSettings {
+    fn default() -> Self {
+        Self {
+            target: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+/// How solutions and data files are laid out in the workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// One year per workspace: `src/bin/05.rs`, `data/inputs/05.txt`.
+    #[default]
+    Flat,
+    /// Several years per workspace: `src/bin/2023-05.rs`, `data/2023/inputs/05.txt`.
+    Year,
+}
+
+/// Where the year of [`Config::year`] comes from, see [`get`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YearSource {
+    Argument,
+    /// The name of a `YYYY-DD` solution binary.
+    BinaryName,
+    ConfigFile,
+    Env,
+}
+
+impl Display for YearSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YearSource::Argument => write!(f, "the --year argument"),
+            YearSource::BinaryName => write!(f, "the name of the solution binary"),
+            YearSource::ConfigFile => write!(f, "`year` in {CONFIG_FILE}"),
+            YearSource::Env => write!(f, "the AOC_YEAR environment variable"),
+        }
+    }
+}
+
+/// Which implementation of [`AocClient`](crate::template::aoc_client::AocClient) talks to the website.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// Shell out to the `aoc` binary of aoc-cli.
+    #[default]
+    AocCli,
+    /// Use the built-in HTTP client.
+    Native,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientSettings {
+    pub backend: Backend,
+    /// Base url of the website, used by the native backend.
+    pub base_url: String,
+}
+
+impl Default for ClientSettings {
+    fn default() -> Self {
+        Self {
+            backend: Backend::default(),
+            base_url: "https://adventofcode.com".into(),
+        }
+    }
+}
+
+/// A column of the benchmark table in the readme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Day,
+    /// The puzzle title from the downloaded description.
+    Title,
+    Part1,
+    Part2,
+    /// Time of both parts.
+    Total,
+    /// Time spent in `parse` spans of both parts.
+    Parse,
+    Samples,
+    /// Share of the total time of the table.
+    Share,
+    /// Peak heap usage of the hungrier part.
+    Memory,
+    /// Lines of code of the solution file.
+    Lines,
+    Trend,
+}
+
+impl Column {
+    const ALL: [Column; 11] = [
+        Column::Day,
+        Column::Title,
+        Column::Part1,
+        Column::Part2,
+        Column::Total,
+        Column::Parse,
+        Column::Samples,
+        Column::Share,
+        Column::Memory,
+        Column::Lines,
+        Column::Trend,
+    ];
+
+    /// The name of the column in `aoc.toml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Day => "day",
+            Column::Title => "title",
+            Column::Part1 => "part_1",
+            Column::Part2 => "part_2",
+            Column::Total => "total",
+            Column::Parse => "parse",
+            Column::Samples => "samples",
+            Column::Share => "share",
+            Column::Memory => "memory",
+            Column::Lines => "lines",
+            Column::Trend => "trend",
+        }
+    }
+
+    /// The heading of the column in the readme.
+    pub fn header(self) -> &'static str {
+        match self {
+            Column::Day => "Day",
+            Column::Title => "Title",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Parse => "Parse",
+            Column::Samples => "Samples",
+            Column::Share => "Share",
+            Column::Memory => "Memory",
+            Column::Lines => "Lines",
+            Column::Trend => "Trend",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+}
+
+/// Order of the rows of the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest days first.
+    Time,
+}
+
+/// How the benchmark table in the readme is rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSettings {
+    pub columns: Vec<Column>,
+    pub sort: SortOrder,
+    /// Significant figures of times, shares and memory sizes.
+    pub significant_figures: usize,
+}
+
+impl Default for TableSettings {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Day, Column::Part1, Column::Part2, Column::Trend],
+            sort: SortOrder::default(),
+            significant_figures: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The year passed to aoc-cli. Defaults to the `AOC_YEAR` environment variable.
+    pub year: Option<u16>,
+    /// Where [`Config::year`] comes from, [`None`] if no year is set.
+    pub year_source: Option<YearSource>,
+    pub layout: Layout,
+    pub data_dir: PathBuf,
+    pub bin_dir: PathBuf,
+    pub readme: PathBuf,
+    /// The benchmark chart referenced by the readme. Defaults to `benchmarks/chart.svg` in the data folder.
+    pub chart: Option<PathBuf>,
+    pub bench: BenchSettings,
+    pub table: TableSettings,
+    pub client: ClientSettings,
+    /// The active session profile. Inputs of named profiles are kept apart from each other.
+    pub profile: Option<String>,
+    day_bench: HashMap<Day, BenchSettings>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: year_from_env(),
+            year_source: year_from_env().map(|_| YearSource::Env),
+            layout: Layout::default(),
+            data_dir: "data".into(),
+            bin_dir: "src/bin".into(),
+            readme: "README.md".into(),
+            chart: None,
+            bench: BenchSettings::default(),
+            table: TableSettings::default(),
+            client: ClientSettings::default(),
+            profile: None,
+            day_bench: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file at `path`. A missing file yields the default config.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let doc = Document::parse(s)?;
+        let mut config = Self::default();
+
+        if let Some(year) = doc.get("", "year") {
+            config.year = Some(to_int(year, "year")?);
+            config.year_source = Some(YearSource::ConfigFile);
+        }
+
+        if let Some(layout) = doc.get("", "layout") {
+            config.layout = match layout.as_str() {
+                Some("flat") => Layout::Flat,
+                Some("year") => Layout::Year,
+                _ => {
+                    return Err(Error::Invalid(
+                        "`layout` must be either \"flat\" or \"year\"".into(),
+                    ))
+                }
+            };
+        }
+
+        if let Some(paths) = doc.table("paths") {
+            for (key, value) in paths {
+                let path = value
+                    .as_str()
+                    .ok_or_else(|| Error::Invalid(format!("`paths.{key}` must be a string")))?
+                    .into();
+                match key.as_str() {
+                    "data" => config.data_dir = path,
+                    "bin" => config.bin_dir = path,
+                    "readme" => config.readme = path,
+                    "chart" => config.chart = Some(path),
+                    _ => return Err(Error::Invalid(format!("unknown key `paths.{key}`"))),
+                }
+            }
+        }
+
+        if let Some(client) = doc.table("client") {
+            for (key, value) in client {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| Error::Invalid(format!("`client.{key}` must be a string")))?;
+                match (key.as_str(), value) {
+                    ("backend", "aoc-cli") => config.client.backend = Backend::AocCli,
+                    ("backend", "native") => config.client.backend = Backend::Native,
+                    ("backend", _) => {
+                        return Err(Error::Invalid(
+                            "`client.backend` must be either \"aoc-cli\" or \"native\"".into(),
+                        ))
+                    }
+                    ("base_url", url) => config.client.base_url = url.into(),
+                    _ => return Err(Error::Invalid(format!("unknown key `client.{key}`"))),
+                }
+            }
+        }
+
+        if let Some(bench) = doc.table("bench") {
+            config.bench = parse_bench(bench, config.bench, "bench")?;
+        }
+
+        if let Some(table) = doc.table("benchmarks") {
+            config.table = parse_table_settings(table)?;
+        }
+
+        for (name, table) in doc.subtables("days") {
+            let day = name
+                .parse::<Day>()
+                .map_err(|e| Error::Invalid(format!("`days.{name}`: {e}")))?;
+            let settings = parse_bench(table, config.bench, &format!("days.{name}"))?;
+            config.day_bench.insert(day, settings);
+        }
+
+        Ok(config)
+    }
+
+    /// Bench settings for a day, with per-day overrides applied.
+    pub fn bench_for(&self, day: Day) -> BenchSettings {
+        self.day_bench.get(&day).copied().unwrap_or(self.bench)
+    }
+
+    /// The year used for namespacing paths, if the workspace uses the year layout.
+    /// The year layout without a year is an error, as paths could not be namespaced.
+    pub fn layout_year(&self) -> Result<Option<u16>, Error> {
+        match (self.layout, self.year) {
+            (Layout::Flat, _) => Ok(None),
+            (Layout::Year, Some(year)) => Ok(Some(year)),
+            (Layout::Year, None) => Err(Error::Invalid(format!(
+                "`layout = \"year\"` needs a year. Pass `--year <year>` or set `year` in {CONFIG_FILE}"
+            ))),
+        }
+    }
+
+    /// Like [`Config::layout_year`], for configs that were checked by [`get`].
+    pub fn namespace_year(&self) -> Option<u16> {
+        self.layout_year().ok().flatten()
+    }
+
+    /// The data folder of the current year, e.g. `data` or `data/2023`.
+    pub fn year_data_dir(&self) -> PathBuf {
+        match self.namespace_year() {
+            Some(year) => self.data_dir.join(year.to_string()),
+            None => self.data_dir.clone(),
+        }
+    }
+
+    /// Path of a data file, e.g. `data/inputs/01.txt` or `data/2023/inputs/01.txt`.
+    pub fn data_path(&self, folder: &str, file_name: &str) -> PathBuf {
+        self.year_data_dir().join(folder).join(file_name)
+    }
+
+    /// Path of the benchmark chart, e.g. `data/benchmarks/chart.svg`.
+    pub fn chart_path(&self) -> PathBuf {
+        self.chart
+            .clone()
+            .unwrap_or_else(|| self.data_path("benchmarks", "chart.svg"))
+    }
+
+    /// Path of an input, e.g. `data/inputs/01.txt` or `data/inputs/<profile>/01.txt` for a named profile.
+    pub fn input_path(&self, day: Day) -> PathBuf {
+        match &self.profile {
+            Some(profile) => self.data_path("inputs", &format!("{profile}/{day}.txt")),
+            None => self.data_path("inputs", &format!("{day}.txt")),
+        }
+    }
+
+    pub fn example_path(&self, day: Day) -> PathBuf {
+        self.data_path("examples", &format!("{day}.txt"))
+    }
+
+    pub fn puzzle_path(&self, day: Day) -> PathBuf {
+        self.data_path("puzzles", &format!("{day}.md"))
+    }
+
+    /// Name of the solution binary, e.g. `05` or `2023-05`.
+    pub fn bin_name(&self, day: Day) -> String {
+        match self.namespace_year() {
+            Some(year) => format!("{year}-{day}"),
+            None => day.to_string(),
+        }
+    }
+
+    pub fn bin_path(&self, day: Day) -> PathBuf {
+        self.bin_dir.join(format!("{}.rs", self.bin_name(day)))
+    }
+}
+
+/// Returns the workspace config, loading it from `aoc.toml` on first access.
+/// Exits the process if the file exists but is invalid, or if the year layout is used without a year.
+///
+/// The year is taken from, in order: a `--year` argument, the name of a `YYYY-DD` solution binary,
+/// the config file and the `AOC_YEAR` environment variable.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        let mut config = match Config::load(Path::new(CONFIG_FILE)) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        };
+
+        let year = (year_from_args().map(|year| (year, YearSource::Argument)))
+            .or_else(|| year_from_exe().map(|year| (year, YearSource::BinaryName)));
+        if let Some((year, source)) = year {
+            config.year = Some(year);
+            config.year_source = Some(source);
+        }
+
+        if let Err(e) = config.layout_year() {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+
+        config.profile = session::active_profile();
+
+        config
+    })
+}
+
+/// Arguments that mirror a `--year` override to child invocations.
+pub fn forwarded_args() -> Vec<String> {
+    year_from_args()
+        .map(|year| vec!["--year".into(), year.to_string()])
+        .unwrap_or_default()
+}
+
+fn year_from_args() -> Option<u16> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--year")?;
+    args.get(index + 1)?.parse().ok()
+}
+
+/// Solution binaries of the year layout are called `YYYY-DD`. Their test harnesses are called `YYYY_DD-<hash>`.
+fn year_from_exe() -> Option<u16> {
+    let exe = env::current_exe().ok()?;
+    parse_year_prefix(exe.file_stem()?.to_str()?)
+}
+
+fn parse_year_prefix(name: &str) -> Option<u16> {
+    let (year, rest) = name.split_at_checked(4)?;
+    let day = rest.strip_prefix(['-', '_'])?.get(..2)?;
+
+    if !year.bytes().chain(day.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    day.parse::<Day>().ok()?;
+    year.parse().ok()
+}
+
+fn year_from_env() -> Option<u16> {
+    env::var("AOC_YEAR").ok()?.parse().ok()
+}
+
+fn to_int<T: TryFrom<i64>>(value: &Value, key: &str) -> Result<T, Error> {
+    value
+        .as_integer()
+        .and_then(|i| T::try_from(i).ok())
+        .ok_or_else(|| Error::Invalid(format!("`{key}` must be a positive integer")))
+}
+
+fn parse_bench(table: &Table, base: BenchSettings, prefix: &str) -> Result<BenchSettings, Error> {
+    let mut settings = base;
+
+    for (key, value) in table {
+        let name = format!("{prefix}.{key}");
+        match key.as_str() {
+            "target_ms" => settings.target = Duration::from_millis(to_int(value, &name)?),
+            "min_samples" => settings.min_samples = to_int::<u64>(value, &name)?.into(),
+            "max_samples" => settings.max_samples = to_int::<u64>(value, &name)?.into(),
+            _ => return Err(Error::Invalid(format!("unknown key `{name}`"))),
+        }
+    }
+
+    if settings.min_samples == 0 || settings.min_samples > settings.max_samples {
+        return Err(Error::Invalid(format!(
+            "`{prefix}`: expected 0 < min_samples <= max_samples"
+        )));
+    }
+
+    Ok(settings)
+}
+
+fn parse_table_settings(table: &Table) -> Result<TableSettings, Error> {
+    let mut settings = TableSettings::default();
+
+    for (key, value) in table {
+        match key.as_str() {
+            "columns" => {
+                let columns = value.as_array().ok_or_else(|| {
+                    Error::Invalid("`benchmarks.columns` must be an array of column names".into())
+                })?;
+                settings.columns = columns
+                    .iter()
+                    .map(|column| {
+                        column.as_str().and_then(Column::from_name).ok_or_else(|| {
+                            let names: Vec<&str> = Column::ALL.iter().map(|c| c.name()).collect();
+                            Error::Invalid(format!(
+                                "`benchmarks.columns` must only contain {}",
+                                names.join(", ")
+                            ))
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                if !settings.columns.contains(&Column::Day) {
+                    return Err(Error::Invalid(
+                        "`benchmarks.columns` must contain \"day\"".into(),
+                    ));
+                }
+            }
+            "sort" => {
+                settings.sort = match value.as_str() {
+                    Some("day") => SortOrder::Day,
+                    Some("time") => SortOrder::Time,
+                    _ => {
+                        return Err(Error::Invalid(
+                            "`benchmarks.sort` must be either \"day\" or \"time\"".into(),
+                        ))
+                    }
+                }
+            }
+            "significant_figures" => {
+                settings.significant_figures = to_int(value, "benchmarks.significant_figures")?;
+                if !(1..=6).contains(&settings.significant_figures) {
+                    return Err(Error::Invalid(
+                        "`benchmarks.significant_figures` must be between 1 and 6".into(),
+                    ));
+                }
+            }
+            _ => return Err(Error::Invalid(format!("unknown key `benchmarks.{key}`"))),
+        }
+    }
+
+    Ok(settings)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{
+        parse_year_prefix, Backend, BenchSettings, Column, Config, Layout, SortOrder, YearSource,
+    };
+    use crate::day;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_paths_and_bench_overrides() {
+        let config = Config::parse(
+            "year = 2022\n[paths]\ndata = \"puzzles\"\nreadme = \"docs/README.md\"\n[bench]\ntarget_ms = 500\n[days.05]\nmax_samples = 20\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.year_source, Some(YearSource::ConfigFile));
+        assert_eq!(
+            config.input_path(day!(3)),
+            PathBuf::from("puzzles/inputs/03.txt")
+        );
+        assert_eq!(config.bin_path(day!(3)), PathBuf::from("src/bin/03.rs"));
+        assert_eq!(config.readme, PathBuf::from("docs/README.md"));
+        assert_eq!(
+            config.chart_path(),
+            PathBuf::from("puzzles/benchmarks/chart.svg")
+        );
+        assert_eq!(config.bench_for(day!(1)).target, Duration::from_millis(500));
+        assert_eq!(
+            config.bench_for(day!(5)),
+            BenchSettings {
+                target: Duration::from_millis(500),
+                min_samples: 10,
+                max_samples: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_table_settings() {
+        let config = Config::parse(
+            "[benchmarks]\ncolumns = [\"day\", \"title\", \"total\", \"memory\"]\nsort = \"time\"\nsignificant_figures = 2\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.table.columns,
+            [Column::Day, Column::Title, Column::Total, Column::Memory]
+        );
+        assert_eq!(config.table.sort, SortOrder::Time);
+        assert_eq!(config.table.significant_figures, 2);
+        assert!(Config::parse("[benchmarks]\ncolumns = [\"day\", \"speed\"]\n").is_err());
+        assert!(Config::parse("[benchmarks]\ncolumns = [\"total\"]\n").is_err());
+        assert!(
+            Config::parse("[benchmarks]\ncolumns = \"day\"\n").is_err_and(|e| e
+                .to_string()
+                .contains("`benchmarks.columns` must be an array"))
+        );
+        assert!(Config::parse("[benchmarks]\nsignificant_figures = 0\n").is_err());
+    }
+
+    #[test]
+    fn parses_client_settings() {
+        let config =
+            Config::parse("[client]\nbackend = \"native\"\nbase_url = \"http://127.0.0.1:8080\"\n")
+                .unwrap();
+
+        assert_eq!(config.client.backend, Backend::Native);
+        assert_eq!(config.client.base_url, "http://127.0.0.1:8080");
+        assert!(Config::parse("[client]\nbackend = \"curl\"\n").is_err());
+    }
+
+    #[test]
+    fn namespaces_paths_in_year_layout() {
+        let config = Config::parse("year = 2022\nlayout = \"year\"\n").unwrap();
+
+        assert_eq!(config.layout, Layout::Year);
+        assert_eq!(config.bin_name(day!(5)), "2022-05");
+        assert_eq!(
+            config.bin_path(day!(5)),
+            PathBuf::from("src/bin/2022-05.rs")
+        );
+        assert_eq!(
+            config.input_path(day!(5)),
+            PathBuf::from("data/2022/inputs/05.txt")
+        );
+        assert_eq!(config.layout_year().unwrap(), Some(2022));
+
+        let config = Config {
+            year: None,
+            ..config
+        };
+        assert!(config
+            .layout_year()
+            .is_err_and(|e| e.to_string().contains("needs a year")));
+    }
+
+    #[test]
+    fn parses_year_from_binary_name() {
+        assert_eq!(parse_year_prefix("2022-05"), Some(2022));
+        assert_eq!(parse_year_prefix("2022_05-1f2e3d4c5b6a7988"), Some(2022));
+        assert_eq!(parse_year_prefix("05"), None);
+        assert_eq!(parse_year_prefix("2022-26"), None);
+        assert_eq!(parse_year_prefix("advent_of_code"), None);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Config::parse("[paths]\ninputs = \"x\"\n").is_err());
+        assert!(Config::parse("[days.26]\nmax_samples = 1\n").is_err());
+        assert!(Config::parse("[bench]\nmin_samples = 0\n").is_err());
+        assert!(Config::parse("layout = \"nested\"\n").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Local metadata about downloaded files, used to avoid needless requests.
+//! Records live in `data/downloads/DD.toml` and hold the unix time each file was last fetched.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::config::Config;
+use crate::template::toml_lite::{Document, Value};
+use crate::Day;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DownloadRecord {
+    /// Unix time in seconds of the last input download.
+    pub input: Option<u64>,
+    /// Unix time in seconds of the last puzzle download.
+    pub puzzle: Option<u64>,
+}
+
+impl DownloadRecord {
+    fn from_document(doc: &Document) -> Self {
+        let timestamp = |key: &str| {
+            doc.get("", key)
+                .and_then(Value::as_integer)
+                .and_then(|t| t.try_into().ok())
+        };
+
+        Self {
+            input: timestamp("input"),
+            puzzle: timestamp("puzzle"),
+        }
+    }
+
+    fn to_document(&self) -> Document {
+        let mut doc = Document::new();
+
+        for (key, timestamp) in [("input", self.input), ("puzzle", self.puzzle)] {
+            if let Some(timestamp) = timestamp {
+                let timestamp = i64::try_from(timestamp).unwrap_or(i64::MAX);
+                doc.set("", key, Value::Integer(timestamp));
+            }
+        }
+
+        doc
+    }
+}
+
+fn get_record_path(config: &Config, day: Day) -> PathBuf {
+    config.data_path("downloads", &format!("{day}.toml"))
+}
+
+/// Loads the record for a day. Days without a record yield an empty one.
+pub fn load(config: &Config, day: Day) -> DownloadRecord {
+    fs::read_to_string(get_record_path(config, day))
+        .ok()
+        .and_then(|s| Document::parse(&s).ok())
+        .map(|doc| DownloadRecord::from_document(&doc))
+        .unwrap_or_default()
+}
+
+pub fn save(config: &Config, day: Day, record: &DownloadRecord) -> Result<(), io::Error> {
+    let path = get_record_path(config, day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, record.to_document().to_string())
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Whether the input of a day was downloaded before. Empty files created by `scaffold` do not count.
+pub fn has_input(config: &Config, day: Day) -> bool {
+    fs::metadata(config.input_path(day)).is_ok_and(|m| m.len() > 0)
+}
+
+/// Why a downloaded input is not a real puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidInput {
+    Empty,
+    /// The website asks to log in, which happens for missing or expired session cookies.
+    LoginRequired,
+    NotUnlocked,
+    Html,
+}
+
+impl Display for InvalidInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "the response was empty"),
+            InvalidInput::LoginRequired => write!(
+                f,
+                "the website asks to log in, the session cookie is missing or has expired"
+            ),
+            InvalidInput::NotUnlocked => write!(f, "the puzzle is not unlocked yet"),
+            InvalidInput::Html => write!(f, "the response is a web page instead of an input"),
+        }
+    }
+}
+
+/// Detects the error pages the website serves in place of an input.
+pub fn validate_input(input: &str) -> Result<(), InvalidInput> {
+    let trimmed = input.trim_start();
+    let start = trimmed
+        .get(..trimmed.len().min(256))
+        .unwrap_or(trimmed)
+        .to_ascii_lowercase();
+
+    if trimmed.is_empty() {
+        Err(InvalidInput::Empty)
+    } else if start.contains("please log in to get your puzzle input") {
+        Err(InvalidInput::LoginRequired)
+    } else if start.contains("before it unlocks") || start.starts_with("404 not found") {
+        Err(InvalidInput::NotUnlocked)
+    } else if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        Err(InvalidInput::Html)
+    } else {
+        Ok(())
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// The file an input is downloaded to before it is validated, e.g. `01.txt.download`.
+pub fn download_path(input_path: &Path) -> PathBuf {
+    with_suffix(input_path, ".download")
+}
+
+/// Moves a rejected download out of the way, e.g. to `01.txt.rejected`, so it is not used as input.
+pub fn quarantine(download: &Path, input_path: &Path) -> Result<PathBuf, io::Error> {
+    let target = with_suffix(input_path, ".rejected");
+    fs::rename(download, &target)?;
+    Ok(target)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{validate_input, DownloadRecord, InvalidInput};
+    use crate::template::toml_lite::Document;
+
+    #[test]
+    fn roundtrips_records() {
+        let record = DownloadRecord {
+            input: Some(1_701_406_800),
+            puzzle: None,
+        };
+
+        let doc = Document::parse(&record.to_document().to_string()).unwrap();
+        assert_eq!(DownloadRecord::from_document(&doc), record);
+    }
+
+    #[test]
+    fn detects_invalid_inputs() {
+        assert_eq!(validate_input("1abc2\npqr3stu8vwx\n"), Ok(()));
+        assert_eq!(validate_input(" \n"), Err(InvalidInput::Empty));
+        assert_eq!(
+            validate_input(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(InvalidInput::LoginRequired)
+        );
+        assert_eq!(
+            validate_input("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time."),
+            Err(InvalidInput::NotUnlocked)
+        );
+        assert_eq!(
+            validate_input("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InvalidInput::Html)
+        );
+    }
+}
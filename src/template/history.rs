@@ -0,0 +1,199 @@
+//! An append-only log of benched timings, used to show how solutions evolve over time.
+//! Every timed run of a part adds a line to `data/benchmarks/history.csv`.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::template::{config, downloads};
+use crate::Day;
+
+const HEADER: &str = "timestamp,commit,day,part,nanos";
+
+/// Number of runs shown in the trend column of the readme.
+pub const TREND_RUNS: usize = 8;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Unix time in seconds.
+    pub timestamp: u64,
+    /// The abbreviated hash of `HEAD`, if the workspace is a git repository.
+    pub commit: Option<String>,
+    pub day: Day,
+    pub part: u8,
+    pub nanos: u128,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.trim().split(',');
+        let timestamp = fields.next()?.parse().ok()?;
+        let commit = Some(fields.next()?)
+            .filter(|c| !c.is_empty())
+            .map(String::from);
+        let day = fields.next()?.parse().ok().and_then(Day::new)?;
+        let part = fields.next()?.parse().ok()?;
+        let nanos = fields.next()?.parse().ok()?;
+
+        Some(Self {
+            timestamp,
+            commit,
+            day,
+            part,
+            nanos,
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            self.timestamp,
+            self.commit.as_deref().unwrap_or_default(),
+            self.day.into_inner(),
+            self.part,
+            self.nanos
+        )
+    }
+}
+
+fn get_path() -> PathBuf {
+    config::get().data_path("benchmarks", "history.csv")
+}
+
+/// The commit of the workspace, looked up once per run as both parts of every day are appended.
+fn current_commit() -> Option<String> {
+    static COMMIT: OnceLock<Option<String>> = OnceLock::new();
+    COMMIT.get_or_init(lookup_commit).clone()
+}
+
+fn lookup_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    let commit = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !commit.trim().is_empty()).then(|| commit.trim().to_string())
+}
+
+/// Appends the timing of a part to the history, creating the file if needed.
+/// Debug builds are skipped, their timings would distort the trend.
+pub fn append(day: Day, part: u8, duration: Duration) -> Result<(), io::Error> {
+    if cfg!(debug_assertions) {
+        return Ok(());
+    }
+
+    let path = get_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{HEADER}")?;
+    }
+
+    let entry = Entry {
+        timestamp: downloads::now(),
+        commit: current_commit(),
+        day,
+        part,
+        nanos: duration.as_nanos(),
+    };
+    writeln!(file, "{}", entry.to_line())
+}
+
+/// All entries in the order they were recorded. Malformed lines are skipped.
+pub fn parse(s: &str) -> Vec<Entry> {
+    s.lines().filter_map(Entry::parse).collect()
+}
+
+/// Loads the history of the workspace, which is empty if nothing was timed yet.
+pub fn load() -> Vec<Entry> {
+    fs::read_to_string(get_path())
+        .map(|s| parse(&s))
+        .unwrap_or_default()
+}
+
+/// The timings of the last `n` runs of a part, oldest first.
+pub fn recent(entries: &[Entry], day: Day, part: u8, n: usize) -> Vec<u128> {
+    let timings: Vec<u128> = entries
+        .iter()
+        .filter(|e| e.day == day && e.part == part)
+        .map(|e| e.nanos)
+        .collect();
+    timings[timings.len().saturating_sub(n)..].to_vec()
+}
+
+/// Renders timings as a unicode sparkline, scaled between the fastest and the slowest run.
+pub fn sparkline(timings: &[u128]) -> String {
+    let (Some(min), Some(max)) = (timings.iter().min(), timings.iter().max()) else {
+        return String::new();
+    };
+    let range = max - min;
+
+    timings
+        .iter()
+        .map(|t| {
+            #[allow(clippy::cast_possible_truncation)]
+            let index = ((t - min) * (SPARKS.len() as u128 - 1))
+                .checked_div(range)
+                .unwrap_or_default() as usize;
+            SPARKS[index]
+        })
+        .collect()
+}
+
+/// The trend cell of a day in the readme, e.g. `▃▁▁ / ▇▅▁`. Parts with less than two runs show `-`.
+pub fn trend(entries: &[Entry], day: Day) -> String {
+    let part = |part: u8| {
+        let timings = recent(entries, day, part, TREND_RUNS);
+        if timings.len() < 2 {
+            "-".to_string()
+        } else {
+            sparkline(&timings)
+        }
+    };
+    format!("{} / {}", part(1), part(2))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, recent, sparkline, trend, HEADER};
+    use crate::day;
+
+    #[test]
+    fn parses_entries() {
+        let s = format!("{HEADER}\n1701406800,a1b2c3d,1,1,74130\n1701406801,,1,2,1200\nbroken\n");
+        let entries = parse(&s);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].commit.as_deref(), Some("a1b2c3d"));
+        assert_eq!(entries[1].commit, None);
+        assert_eq!(entries[1].nanos, 1200);
+        assert_eq!(parse(&entries[0].to_line()), [entries[0].clone()]);
+    }
+
+    #[test]
+    fn renders_trends() {
+        assert_eq!(sparkline(&[100, 800, 450, 100]), "▁█▄▁");
+        assert_eq!(sparkline(&[5, 5]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+
+        let lines: Vec<String> = (0..10)
+            .map(|i| format!("{i},,3,1,{}", 1000 - i * 100))
+            .chain(["10,,3,2,500".to_string()])
+            .collect();
+        let entries = parse(&lines.join("\n"));
+
+        assert_eq!(recent(&entries, day!(3), 1, 3), [300, 200, 100]);
+        assert_eq!(trend(&entries, day!(3)), "█▇▆▅▄▃▂▁ / -");
+        assert_eq!(trend(&entries, day!(4)), "- / -");
+    }
+}
@@ -0,0 +1,280 @@
+//! A minimal HTTP/1.1 client on top of `std::net`.
+//! Only plain `http://` URLs are supported, as there is no TLS implementation in the standard library.
+use std::{
+    fmt::Display,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum Error {
+    UnsupportedUrl(String),
+    /// `https://` urls need TLS, which the standard library does not implement.
+    Https(String),
+    BadResponse(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnsupportedUrl(url) => write!(
+                f,
+                "unsupported url \"{url}\", only \"http://host[:port][/path]\" urls are supported."
+            ),
+            Error::Https(url) => write!(
+                f,
+                "the native client cannot connect to \"{url}\", it has no TLS support. Set `client.base_url` to an \"http://\" url (e.g. a local HTTPS proxy) or use `client.backend = \"aoc-cli\"`."
+            ),
+            Error::BadResponse(msg) => write!(f, "malformed http response: {msg}"),
+            Error::IO(e) => write!(f, "http request failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The parts of an `http://` base url.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseUrl {
+    pub host: String,
+    pub port: u16,
+    /// Path prefix without trailing slash, e.g. `""` or `"/aoc"`.
+    pub path: String,
+}
+
+impl BaseUrl {
+    pub fn parse(url: &str) -> Result<Self, Error> {
+        let unsupported = || Error::UnsupportedUrl(url.into());
+
+        if url.starts_with("https://") {
+            return Err(Error::Https(url.into()));
+        }
+
+        let rest = url.strip_prefix("http://").ok_or_else(unsupported)?;
+        let (authority, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| unsupported())?),
+            None => (authority, 80),
+        };
+
+        if host.is_empty() {
+            return Err(unsupported());
+        }
+
+        Ok(Self {
+            host: host.into(),
+            port,
+            path: path.trim_end_matches('/').into(),
+        })
+    }
+}
+
+impl Display for BaseUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "http://{}:{}{}", self.host, self.port, self.path)
+    }
+}
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends a request to `path` (relative to `base`) and reads the whole response.
+pub fn request(
+    base: &BaseUrl,
+    method: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, Error> {
+    let mut stream = TcpStream::connect((base.host.as_str(), base.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut req = format!(
+        "{method} {}{path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        base.path, base.host
+    );
+    for (name, value) in headers {
+        req.push_str(&format!("{name}: {value}\r\n"));
+    }
+    if let Some(body) = body {
+        req.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    req.push_str("\r\n");
+    if let Some(body) = body {
+        req.push_str(body);
+    }
+
+    stream.write_all(req.as_bytes())?;
+    stream.flush()?;
+
+    read_response(BufReader::new(stream))
+}
+
+fn read_response(mut reader: impl BufRead) -> Result<Response, Error> {
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| Error::BadResponse(format!("invalid status line {status_line:?}")))?;
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut response = Response {
+        status,
+        headers,
+        body: String::new(),
+    };
+
+    let mut body = vec![];
+    if response
+        .header("transfer-encoding")
+        .is_some_and(|v| v.eq_ignore_ascii_case("chunked"))
+    {
+        read_chunked(&mut reader, &mut body)?;
+    } else if let Some(len) = response.header("content-length") {
+        let len: u64 = len
+            .parse()
+            .map_err(|_| Error::BadResponse(format!("invalid content-length {len:?}")))?;
+        reader.take(len).read_to_end(&mut body)?;
+    } else {
+        reader.read_to_end(&mut body)?;
+    }
+
+    response.body = String::from_utf8_lossy(&body).into_owned();
+    Ok(response)
+}
+
+fn read_chunked(reader: &mut impl BufRead, body: &mut Vec<u8>) -> Result<(), Error> {
+    loop {
+        let mut size_line = String::new();
+        reader.read_line(&mut size_line)?;
+        let size_str = size_line.trim().split(';').next().unwrap_or_default();
+        let size = usize::from_str_radix(size_str, 16)
+            .map_err(|_| Error::BadResponse(format!("invalid chunk size {size_line:?}")))?;
+
+        if size == 0 {
+            return Ok(());
+        }
+
+        let mut chunk = vec![0; size];
+        reader.read_exact(&mut chunk)?;
+        body.extend_from_slice(&chunk);
+
+        // every chunk is terminated by CRLF.
+        let mut crlf = String::new();
+        reader.read_line(&mut crlf)?;
+    }
+}
+
+/// Encodes a value for an `application/x-www-form-urlencoded` body.
+pub fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".into(),
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{form_encode, read_response, BaseUrl, Error};
+
+    #[test]
+    fn parses_base_urls() {
+        let url = BaseUrl::parse("http://127.0.0.1:8080/aoc/").unwrap();
+        assert_eq!(url.host, "127.0.0.1");
+        assert_eq!(url.port, 8080);
+        assert_eq!(url.path, "/aoc");
+
+        assert_eq!(BaseUrl::parse("http://localhost").unwrap().port, 80);
+        assert!(matches!(
+            BaseUrl::parse("https://adventofcode.com"),
+            Err(Error::Https(_))
+        ));
+        assert!(matches!(
+            BaseUrl::parse("adventofcode.com"),
+            Err(Error::UnsupportedUrl(_))
+        ));
+    }
+
+    #[test]
+    fn reads_chunked_responses() {
+        let raw = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n";
+        let response = read_response(raw.as_bytes()).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "hello world");
+    }
+
+    #[test]
+    fn reads_sized_responses() {
+        let raw = "HTTP/1.1 404 Not Found\r\ncontent-length: 3\r\n\r\nabcdef";
+        let response = read_response(raw.as_bytes()).unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "abc");
+    }
+
+    #[test]
+    fn encodes_form_values() {
+        assert_eq!(form_encode("a b&c=1"), "a+b%26c%3D1");
+    }
+}
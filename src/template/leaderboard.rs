@@ -0,0 +1,235 @@
+//! Reads private leaderboards from the JSON API of the website and renders them as terminal tables.
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::template::json_lite::{ParseError, Value};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u64,
+    pub local_score: u64,
+    pub last_star_ts: u64,
+    /// Unix times at which the stars of part one and part two were earned, per day.
+    pub days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    /// The name shown on the website, which is a placeholder for anonymous users.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Seconds between the first and the second star of a day.
+    pub fn delta(&self, day: u8) -> Option<u64> {
+        match self.days.get(&day)? {
+            [Some(first), Some(second)] => Some(second.saturating_sub(*first)),
+            _ => None,
+        }
+    }
+
+    fn from_json(id: &str, value: &Value) -> Self {
+        let number = |key: &str| value.get(key).and_then(Value::as_u64).unwrap_or_default();
+
+        let days = value
+            .get("completion_day_level")
+            .and_then(Value::as_object)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|(day, levels)| {
+                let timestamp = |level: &str| {
+                    levels
+                        .get(level)
+                        .and_then(|l| l.get("get_star_ts"))
+                        .and_then(Value::as_u64)
+                };
+                Some((day.parse().ok()?, [timestamp("1"), timestamp("2")]))
+            })
+            .collect();
+
+        Self {
+            id: value
+                .get("id")
+                .and_then(Value::as_u64)
+                .or_else(|| id.parse().ok())
+                .unwrap_or_default(),
+            name: value.get("name").and_then(Value::as_str).map(String::from),
+            stars: number("stars"),
+            local_score: number("local_score"),
+            last_star_ts: number("last_star_ts"),
+            days,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub event: Option<u64>,
+    /// Members ranked like on the website: by local score, then by who got their last star first.
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, ParseError> {
+        let value = Value::parse(json)?;
+
+        let mut members: Vec<Member> = value
+            .get("members")
+            .and_then(Value::as_object)
+            .unwrap_or_default()
+            .iter()
+            .map(|(id, member)| Member::from_json(id, member))
+            .collect();
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Self {
+            event: value.get("event").and_then(Value::as_u64),
+            members,
+        })
+    }
+
+    /// The last day on which any member earned a star.
+    fn last_day(&self) -> u8 {
+        self.members
+            .iter()
+            .filter_map(|m| m.days.keys().max())
+            .max()
+            .copied()
+            .unwrap_or(1)
+    }
+
+    /// Renders the ranking with the stars per day, followed by the time each member needed for part two.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let days = 1..=self.last_day();
+
+        let _ = write!(
+            out,
+            "{ANSI_ITALIC}{:>4} {:>5} {:>5} ",
+            "#", "score", "stars"
+        );
+        for day in days.clone() {
+            let _ = write!(out, " {day:02}");
+        }
+        let _ = writeln!(out, "  name{ANSI_RESET}");
+
+        for (rank, member) in self.members.iter().enumerate() {
+            let _ = write!(
+                out,
+                "{:>3}) {:>5} {:>5} ",
+                rank + 1,
+                member.local_score,
+                member.stars
+            );
+            for day in days.clone() {
+                let cell = match member.days.get(&day) {
+                    Some([Some(_), Some(_)]) => "★★",
+                    Some([Some(_), None]) => "★☆",
+                    _ => "··",
+                };
+                let _ = write!(out, " {cell}");
+            }
+            let _ = writeln!(out, "  {ANSI_BOLD}{}{ANSI_RESET}", member.display_name());
+        }
+
+        let solved_days: Vec<u8> = days
+            .filter(|&day| self.members.iter().any(|m| m.delta(day).is_some()))
+            .collect();
+
+        if solved_days.is_empty() {
+            return out;
+        }
+
+        let _ = writeln!(out, "\nTime from part one to part two:");
+        let _ = write!(out, "{ANSI_ITALIC}{:<24}", "name");
+        for day in &solved_days {
+            let _ = write!(out, " {day:>7}");
+        }
+        let _ = writeln!(out, "{ANSI_RESET}");
+
+        for member in &self.members {
+            let name: String = member.display_name().chars().take(24).collect();
+            let _ = write!(out, "{name:<24}");
+            for &day in &solved_days {
+                let delta = member.delta(day).map(format_delta).unwrap_or_default();
+                let _ = write!(out, " {delta:>7}");
+            }
+            let _ = writeln!(out);
+        }
+
+        out
+    }
+}
+
+/// Formats a number of seconds with its two largest units, e.g. `4m12s` or `1d03h`.
+pub fn format_delta(secs: u64) -> String {
+    let (days, hours, mins) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60);
+    if days > 0 {
+        format!("{days}d{hours:02}h")
+    } else if hours > 0 {
+        format!("{hours}h{mins:02}m")
+    } else if mins > 0 {
+        format!("{mins}m{:02}s", secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_delta, Leaderboard};
+
+    const FIXTURE: &str = include_str!("fixtures/leaderboard.json");
+
+    #[test]
+    fn parses_and_ranks_members() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.event, Some(2023));
+
+        let names: Vec<String> = leaderboard
+            .members
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        // equal scores are ranked by who got their last star first.
+        assert_eq!(
+            names,
+            ["Grace \"G\" Hopper", "Ada", "(anonymous user #200)"]
+        );
+
+        let ada = &leaderboard.members[1];
+        assert_eq!(ada.delta(1), Some(252));
+        assert_eq!(ada.delta(3), None);
+    }
+
+    #[test]
+    fn renders_tables() {
+        let rendered = Leaderboard::parse(FIXTURE).unwrap().render();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert!(lines[0].contains(" 01 02 03  name"));
+        assert!(lines[1].starts_with("  1)    27     4  ★★ ★★ ··"));
+        assert!(lines[2].starts_with("  2)    27     5  ★★ ★★ ★☆"));
+        assert!(lines[3].starts_with("  3)     8     2  ★☆ ★☆ ··"));
+        assert!(lines[7].starts_with("Grace \"G\" Hopper           1m00s   1d00h"));
+        assert!(lines[8].starts_with("Ada                        4m12s   1h05m"));
+    }
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(format_delta(42), "42s");
+        assert_eq!(format_delta(252), "4m12s");
+        assert_eq!(format_delta(3900), "1h05m");
+        assert_eq!(format_delta(86700), "1d00h");
+    }
+}
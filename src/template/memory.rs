@@ -0,0 +1,81 @@
+//! Measures the peak heap usage of solutions with a counting allocator.
+//! The allocator is opt-in with the `memory` feature, as counting slows down every allocation.
+//! The `solution!` macro installs it in solution binaries; with the `registry` feature, solutions run
+//! in the binary of the template, so the library installs it instead.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator and keeps track of the allocated bytes.
+pub struct CountingAllocator;
+
+#[cfg(all(feature = "memory", feature = "registry"))]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+impl CountingAllocator {
+    fn added(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn removed(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::added(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::added(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::removed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::removed(layout.size());
+            Self::added(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Starts a measurement. Returns the bytes allocated at this point, to be passed to [`peak_since`].
+pub fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+/// The most bytes allocated on top of `baseline` since [`reset_peak`].
+/// Returns [`None`] if the counting allocator is not installed, i.e. without the `memory` feature.
+pub fn peak_since(baseline: usize) -> Option<u64> {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let peak = PEAK.load(Ordering::Relaxed).saturating_sub(baseline);
+    u64::try_from(peak).ok()
+}
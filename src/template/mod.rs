@@ -1,10 +1,30 @@
 use crate::Day;
-use std::{env, fs};
+use std::fs;
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod benchmark_chart;
+pub mod benchmark_export;
 pub mod commands;
+pub mod config;
+pub mod downloads;
+pub mod history;
+pub mod http;
+pub mod json_lite;
+pub mod leaderboard;
+pub mod memory;
+pub mod native_client;
+pub mod profiler;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+#[cfg(feature = "registry")]
+pub mod registry;
 pub mod runner;
+pub mod session;
+pub mod test_report;
+pub mod toml_lite;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -13,20 +33,22 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = config::get().data_path(folder, &format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads the input of a day, taking the active session profile into account.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let f = fs::read_to_string(config::get().input_path(day));
+    f.expect("could not open input file")
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = config::get().data_path(folder, &format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -38,9 +60,15 @@ macro_rules! solution {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        // counts allocations to report the peak memory of each part.
+        #[cfg(all(feature = "memory", not(feature = "registry")))]
+        #[global_allocator]
+        static ALLOCATOR: advent_of_code::template::memory::CountingAllocator =
+            advent_of_code::template::memory::CountingAllocator;
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
+            let input = advent_of_code::template::read_input(DAY);
             run_part(part_one, &input, DAY, 1);
             run_part(part_two, &input, DAY, 2);
         }
@@ -0,0 +1,468 @@
+//! Built-in client for the Advent of Code website, used when `client.backend = "native"`.
+//! Puzzle descriptions are converted to the same markdown flavor that aoc-cli writes.
+use std::{fs, path::Path};
+
+use crate::template::{
+    aoc_client::{AocClient, Error, SubmitVerdict},
+    config,
+    http::{self, BaseUrl, Response},
+    session,
+};
+use crate::Day;
+
+const USER_AGENT: &str = concat!("advent_of_code template ", env!("CARGO_PKG_VERSION"));
+
+pub struct NativeClient {
+    base_url: String,
+    session: Option<String>,
+    year: Option<u16>,
+}
+
+impl NativeClient {
+    pub fn new(base_url: &str, session: Option<String>, year: Option<u16>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            session,
+            year,
+        }
+    }
+
+    pub fn from_config() -> Self {
+        let config = config::get();
+        Self::new(
+            &config.client.base_url,
+            session::read_session(),
+            config.year,
+        )
+    }
+
+    fn send(&self, method: &str, path: &str, body: Option<&str>) -> Result<Response, Error> {
+        let base = BaseUrl::parse(&self.base_url)?;
+        let session = self.session.as_ref().ok_or(Error::MissingSession)?;
+        let cookie = format!("session={session}");
+
+        let mut headers = vec![("Cookie", cookie.as_str()), ("User-Agent", USER_AGENT)];
+        if body.is_some() {
+            headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+        }
+
+        let response = http::request(&base, method, path, &headers, body)?;
+
+        if response.is_success() {
+            Ok(response)
+        } else {
+            Err(Error::Status(response.status))
+        }
+    }
+
+    fn day_path(&self, day: Day) -> Result<String, Error> {
+        let year = self.year.ok_or(Error::MissingYear)?;
+        Ok(format!("/{year}/day/{}", day.into_inner()))
+    }
+
+    pub fn fetch_input(&self, day: Day) -> Result<String, Error> {
+        let path = format!("{}/input", self.day_path(day)?);
+        Ok(self.send("GET", &path, None)?.body)
+    }
+
+    /// Fetches the puzzle description as markdown.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, Error> {
+        let html = self.send("GET", &self.day_path(day)?, None)?.body;
+        Ok(html_to_markdown(&extract_description(&html)))
+    }
+
+    /// Fetches the JSON of a private leaderboard.
+    pub fn fetch_leaderboard_json(&self, id: u64) -> Result<String, Error> {
+        let year = self.year.ok_or(Error::MissingYear)?;
+        let path = format!("/{year}/leaderboard/private/view/{id}.json");
+        Ok(self.send("GET", &path, None)?.body)
+    }
+
+    /// Posts an answer and returns the message of the response page as markdown.
+    pub fn post_answer(&self, day: Day, part: u8, answer: &str) -> Result<String, Error> {
+        let path = format!("{}/answer", self.day_path(day)?);
+        let body = format!("level={part}&answer={}", http::form_encode(answer));
+        let html = self.send("POST", &path, Some(&body))?.body;
+        Ok(html_to_markdown(
+            &extract_elements(&html, "article").join("\n"),
+        ))
+    }
+}
+
+impl AocClient for NativeClient {
+    fn check(&self) -> Result<(), Error> {
+        BaseUrl::parse(&self.base_url)?;
+        if self.session.is_none() {
+            return Err(Error::MissingSession);
+        }
+        if self.year.is_none() {
+            return Err(Error::MissingYear);
+        }
+        Ok(())
+    }
+
+    fn validate_session(&self) -> Result<(), Error> {
+        // the settings page is only available to logged in users.
+        match self.send("GET", "/settings", None) {
+            Ok(_) => Ok(()),
+            Err(Error::Status(_)) => Err(Error::InvalidSession),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn download_input(&self, day: Day, path: &Path) -> Result<(), Error> {
+        write_file(path, &self.fetch_input(day)?)
+    }
+
+    fn download_puzzle(&self, day: Day) -> Result<(), Error> {
+        write_file(&config::get().puzzle_path(day), &self.fetch_puzzle(day)?)
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<SubmitVerdict, Error> {
+        let message = self.post_answer(day, part, answer)?;
+        println!("{message}");
+        SubmitVerdict::parse(&message).ok_or_else(|| Error::UnknownVerdict(message.trim().into()))
+    }
+
+    fn fetch_leaderboard(&self, id: u64) -> Result<String, Error> {
+        self.fetch_leaderboard_json(id)
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Returns the outer html of every `<tag>` element, without support for nesting of the same tag.
+fn extract_elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut elements = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let Some(len) = rest[start..].find(&close) else {
+            break;
+        };
+        let end = start + len + close.len();
+        elements.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    elements
+}
+
+/// Keeps the puzzle articles and the "Your puzzle answer was" paragraphs that follow them.
+fn extract_description(html: &str) -> String {
+    let main = extract_elements(html, "main")
+        .first()
+        .copied()
+        .unwrap_or(html);
+
+    let mut parts = vec![];
+    let mut rest = main;
+
+    loop {
+        let article = rest.find("<article");
+        let answer = rest.find("<p>Your puzzle answer was");
+
+        let (start, tag) = match (article, answer) {
+            (Some(a), Some(p)) if p < a => (p, "p"),
+            (Some(a), _) => (a, "article"),
+            (None, Some(p)) => (p, "p"),
+            (None, None) => break,
+        };
+
+        let Some(element) = extract_elements(&rest[start..], tag).first().copied() else {
+            break;
+        };
+        parts.push(element);
+        rest = &rest[start + element.len()..];
+    }
+
+    parts.join("\n")
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Converts the subset of html used in puzzle descriptions to markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+
+    let push_text = |out: &mut String, text: &str, in_pre: bool| {
+        let text = decode_entities(text);
+        if in_pre {
+            out.push_str(&text);
+            return;
+        }
+        for (i, word) in text.split_whitespace().enumerate() {
+            let at_line_start = out.is_empty() || out.ends_with('\n');
+            let needs_space = i > 0 || text.starts_with(char::is_whitespace);
+            if needs_space && !at_line_start && !out.ends_with(' ') {
+                out.push(' ');
+            }
+            out.push_str(word);
+        }
+        if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+            out.push(' ');
+        }
+    };
+
+    while let Some(lt) = rest.find('<') {
+        push_text(&mut out, &rest[..lt], in_pre);
+
+        let Some(gt) = rest[lt..].find('>').map(|i| lt + i) else {
+            rest = "";
+            break;
+        };
+
+        let tag = &rest[lt + 1..gt];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                end_block(&mut out);
+                out.push('\\');
+            }
+            ("h2", true) => {
+                trim_trailing_spaces(&mut out);
+                out.push_str("\n----------\n\n");
+            }
+            ("p", true) => {
+                trim_trailing_spaces(&mut out);
+                out.push_str("\n\n");
+            }
+            ("p", false) | ("ul", _) => end_block(&mut out),
+            ("pre", false) => {
+                end_block(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+                in_pre = false;
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) => out.push('*'),
+            ("li", false) => {
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("* ");
+            }
+            ("li", true) => {
+                trim_trailing_spaces(&mut out);
+                out.push('\n');
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[gt + 1..];
+    }
+
+    push_text(&mut out, rest, in_pre);
+
+    let mut markdown = out.trim_end().to_string();
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+    markdown.push('\n');
+    markdown
+}
+
+fn trim_trailing_spaces(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+}
+
+/// Starts a new block, separated from the previous one by a blank line.
+fn end_block(out: &mut String) {
+    trim_trailing_spaces(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push_str(if out.ends_with('\n') { "\n" } else { "\n\n" });
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    use super::{html_to_markdown, NativeClient};
+    use crate::day;
+
+    const PUZZLE_HTML: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with
+global snow production.</p>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+<ul><li>one &amp; two</li><li>three</li></ul>
+</article>
+<p>Your puzzle answer was <code>54990</code>.</p>
+<form method="post"><input type="text" name="answer"/></form>
+</main></body></html>"#;
+
+    /// Serves `responses` in order and reports each received request head and body.
+    fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+
+                tx.send(request).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    fn ok(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn converts_puzzle_html_to_markdown() {
+        let markdown = html_to_markdown(&super::extract_description(PUZZLE_HTML));
+        let expected = [
+            "\\--- Day 1: Trebuchet?! ---",
+            "----------",
+            "",
+            "Something is *wrong* with global snow production.",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "Adding these together produces `*142*`.",
+            "",
+            "* one & two",
+            "* three",
+            "",
+            "Your puzzle answer was `54990`.",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(markdown, expected);
+    }
+
+    #[test]
+    fn fetches_input_and_puzzle() {
+        let (url, requests) = serve(vec![ok("1abc2\n"), ok(PUZZLE_HTML)]);
+        let client = NativeClient::new(&url, Some("cookie".into()), Some(2023));
+
+        assert_eq!(client.fetch_input(day!(1)).unwrap(), "1abc2\n");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=cookie\r\n"));
+
+        assert!(client
+            .fetch_puzzle(day!(1))
+            .unwrap()
+            .starts_with("\\--- Day 1"));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /2023/day/1 HTTP/1.1\r\n"));
+    }
+
+    #[test]
+    fn posts_answers() {
+        let (url, requests) = serve(vec![ok(
+            "<main><article><p>That's the right answer!</p></article></main>",
+        )]);
+        let client = NativeClient::new(&url, Some("cookie".into()), Some(2023));
+
+        let message = client.post_answer(day!(5), 2, "4 2").unwrap();
+        assert_eq!(message, "That's the right answer!\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/5/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=4+2"));
+    }
+
+    #[test]
+    fn reports_error_status() {
+        let (url, _requests) = serve(vec![
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n".into(),
+        ]);
+        let client = NativeClient::new(&url, Some("cookie".into()), Some(2023));
+        assert!(matches!(
+            client.fetch_input(day!(1)),
+            Err(super::Error::Status(400))
+        ));
+    }
+
+    #[test]
+    fn rejects_https_base_urls() {
+        use crate::template::{aoc_client::AocClient, http};
+
+        let client = NativeClient::new(
+            "https://adventofcode.com",
+            Some("cookie".into()),
+            Some(2023),
+        );
+        assert!(matches!(
+            client.check(),
+            Err(super::Error::Http(http::Error::Https(_)))
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+//! A lightweight hierarchical profiler for solutions.
+//! Spans record wall-clock time into a thread-local tree and are aggregated by their position in the tree.
+//! While profiling is disabled, entering a span only costs an atomic load.
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static TREE: RefCell<Tree> = RefCell::new(Tree::default());
+}
+
+/// Records the time until the end of the enclosing block as a span named `$name`.
+///
+/// ```
+/// fn part_one(input: &str) -> Option<u32> {
+///     let grid = {
+///         advent_of_code::span!("parse");
+///         parse(input)
+///     };
+///     advent_of_code::span!("solve");
+///     solve(&grid)
+/// }
+/// ```
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        let _span_guard = $crate::template::profiler::SpanGuard::enter($name);
+    };
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Ends its span when dropped. Create it with [`span!`](crate::span).
+pub struct SpanGuard {
+    start: Option<Instant>,
+}
+
+impl SpanGuard {
+    #[must_use]
+    pub fn enter(name: &'static str) -> Self {
+        if !is_enabled() {
+            return Self { start: None };
+        }
+
+        TREE.with(|tree| tree.borrow_mut().enter(name));
+        Self {
+            start: Some(Instant::now()),
+        }
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let elapsed = start.elapsed();
+            TREE.with(|tree| tree.borrow_mut().exit(elapsed));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    name: &'static str,
+    calls: u64,
+    total: Duration,
+    children: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+struct Tree {
+    nodes: Vec<Node>,
+    roots: Vec<usize>,
+    stack: Vec<usize>,
+}
+
+impl Tree {
+    fn enter(&mut self, name: &'static str) {
+        let siblings = match self.stack.last() {
+            Some(&parent) => &self.nodes[parent].children,
+            None => &self.roots,
+        };
+
+        let index = match siblings.iter().find(|&&i| self.nodes[i].name == name) {
+            Some(&index) => index,
+            None => {
+                let index = self.nodes.len();
+                self.nodes.push(Node {
+                    name,
+                    calls: 0,
+                    total: Duration::ZERO,
+                    children: vec![],
+                });
+                match self.stack.last() {
+                    Some(&parent) => self.nodes[parent].children.push(index),
+                    None => self.roots.push(index),
+                }
+                index
+            }
+        };
+
+        self.stack.push(index);
+    }
+
+    fn exit(&mut self, elapsed: Duration) {
+        if let Some(index) = self.stack.pop() {
+            let node = &mut self.nodes[index];
+            node.calls += 1;
+            node.total += elapsed;
+        }
+    }
+}
+
+/// The aggregated span tree of the current thread.
+pub struct Report {
+    tree: Tree,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.tree.nodes.is_empty()
+    }
+
+    /// Time spent in all spans called `name`, averaged over the calls of the root spans.
+    pub fn per_call(&self, name: &str) -> Option<Duration> {
+        let nodes = &self.tree.nodes;
+        let calls: u64 = self.tree.roots.iter().map(|&i| nodes[i].calls).sum();
+        let calls = u32::try_from(calls).ok().filter(|c| *c > 0)?;
+
+        let spans: Vec<&Node> = nodes.iter().filter(|node| node.name == name).collect();
+        (!spans.is_empty()).then(|| spans.iter().map(|node| node.total).sum::<Duration>() / calls)
+    }
+
+    fn fmt_node(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        index: usize,
+        depth: usize,
+        total: Duration,
+    ) -> std::fmt::Result {
+        let node = &self.tree.nodes[index];
+        let share = if total.is_zero() {
+            0.0
+        } else {
+            node.total.as_secs_f64() / total.as_secs_f64() * 100.0
+        };
+        let name = format!("{}{}", "  ".repeat(depth), node.name);
+
+        writeln!(
+            f,
+            "{name:<32} {:>12} {share:>6.1}% {:>8}×",
+            format!("{:.1?}", node.total),
+            node.calls
+        )?;
+
+        for &child in &node.children {
+            self.fmt_node(f, child, depth + 1, total)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = self
+            .tree
+            .roots
+            .iter()
+            .map(|&i| self.tree.nodes[i].total)
+            .sum();
+
+        for &root in &self.tree.roots {
+            self.fmt_node(f, root, 0, total)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Takes the span tree recorded on the current thread, leaving an empty tree behind.
+pub fn take_report() -> Report {
+    Report {
+        tree: TREE.with(|tree| std::mem::take(&mut *tree.borrow_mut())),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{enable, take_report};
+
+    #[test]
+    fn aggregates_nested_spans() {
+        enable();
+        take_report();
+
+        for _ in 0..3 {
+            crate::span!("part_one");
+            {
+                crate::span!("parse");
+            }
+            crate::span!("solve");
+        }
+
+        let report = take_report();
+        let tree = &report.tree;
+
+        assert_eq!(tree.roots.len(), 1);
+        let root = &tree.nodes[tree.roots[0]];
+        assert_eq!(root.name, "part_one");
+        assert_eq!(root.calls, 3);
+
+        let children: Vec<_> = root
+            .children
+            .iter()
+            .map(|&i| (tree.nodes[i].name, tree.nodes[i].calls))
+            .collect();
+        assert_eq!(children, [("parse", 3), ("solve", 3)]);
+        assert_eq!(
+            report.per_call("parse"),
+            Some(tree.nodes[root.children[0]].total / 3)
+        );
+        assert_eq!(report.per_call("unknown"), None);
+        assert!(take_report().is_empty());
+    }
+}
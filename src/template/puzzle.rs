@@ -0,0 +1,358 @@
+//! Helpers for reading the puzzle descriptions saved by `download`.
+//! The markdown looks the same for both backends: a `\--- Day 1: ... ---` heading per part,
+//! fenced code blocks for preformatted text and `*...*` for highlighted text.
+use std::fs;
+
+use crate::template::{config, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// Code blocks are indented by this gutter.
+const CODE_GUTTER: &str = "  │ ";
+
+/// A fenced code block of the puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose section contains the block.
+    pub part: u8,
+    pub content: String,
+}
+
+impl CodeBlock {
+    pub fn line_count(&self) -> usize {
+        self.content.lines().count()
+    }
+}
+
+/// Reads the saved puzzle description of a day.
+pub fn read(day: Day) -> Option<String> {
+    fs::read_to_string(config::get().puzzle_path(day)).ok()
+}
+
+/// The title of a puzzle, e.g. `Trebuchet?!` for `\--- Day 1: Trebuchet?! ---`.
+pub fn title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim().trim_end_matches('-').trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+fn is_part_two_heading(line: &str) -> bool {
+    line.contains("--- Part Two ---")
+}
+
+/// Splits the description into the sections of part one and part two.
+/// The second section is empty until part one has been solved.
+pub fn sections(markdown: &str) -> [String; 2] {
+    let mut sections = [String::new(), String::new()];
+    let mut index = 0;
+
+    for line in markdown.lines() {
+        if is_part_two_heading(line) {
+            index = 1;
+        }
+        sections[index].push_str(line);
+        sections[index].push('\n');
+    }
+
+    sections
+}
+
+/// Returns all fenced code blocks in order of appearance.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut part = 1;
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(content) => blocks.push(CodeBlock { part, content }),
+                None => current = Some(String::new()),
+            }
+            continue;
+        }
+
+        match current.as_mut() {
+            Some(content) => {
+                content.push_str(line);
+                content.push('\n');
+            }
+            None if is_part_two_heading(line) => part = 2,
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+/// Returns the highlighted code spans of a section, e.g. `142` for `` `*142*` `` or ``*`142`*``.
+pub fn highlighted_values(section: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut in_block = false;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+        if in_block {
+            continue;
+        }
+
+        // odd segments are inside of code spans.
+        let segments: Vec<&str> = line.split('`').collect();
+        for i in (1..segments.len().saturating_sub(1)).step_by(2) {
+            let code = segments[i];
+            let value = match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                Some(value) => value,
+                None if segments[i - 1].ends_with('*') && segments[i + 1].starts_with('*') => code,
+                None => continue,
+            };
+            if !value.is_empty() {
+                values.push(value.to_string());
+            }
+        }
+    }
+
+    values
+}
+
+/// Guesses the expected example answer of each part: the last highlighted code span of its section.
+pub fn expected_answers(markdown: &str) -> [Option<String>; 2] {
+    sections(markdown).map(|section| highlighted_values(&section).pop())
+}
+
+/// A word of styled text and its width on screen.
+struct Word {
+    text: String,
+    width: usize,
+}
+
+/// Splits inline markdown into words, turning `*...*` and highlighted code into bold text.
+fn style_words(text: &str) -> Vec<Word> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = vec![];
+    let mut word = Word {
+        text: String::new(),
+        width: 0,
+    };
+    let mut in_code = false;
+    let mut bold = false;
+
+    let mut finish = |word: &mut Word, bold: bool| {
+        if word.width > 0 {
+            if bold {
+                word.text.push_str(ANSI_RESET);
+            }
+            words.push(std::mem::replace(
+                word,
+                Word {
+                    text: String::new(),
+                    width: 0,
+                },
+            ));
+        }
+        word.text.clear();
+        if bold {
+            word.text.push_str(ANSI_BOLD);
+        }
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let at_code_edge = i > 0 && chars[i - 1] == '`' || chars.get(i + 1) == Some(&'`');
+
+        match c {
+            c if c.is_whitespace() => finish(&mut word, bold),
+            '`' => in_code = !in_code,
+            '*' if !in_code || at_code_edge => {
+                bold = !bold;
+                word.text
+                    .push_str(if bold { ANSI_BOLD } else { ANSI_RESET });
+            }
+            '\\' if !in_code && i + 1 < chars.len() => {
+                i += 1;
+                word.text.push(chars[i]);
+                word.width += 1;
+            }
+            c => {
+                word.text.push(c);
+                word.width += 1;
+            }
+        }
+        i += 1;
+    }
+    finish(&mut word, bold);
+
+    words
+}
+
+/// Wraps styled text to `width` columns, prefixing every line with `indent`.
+/// The first line uses `first_indent` instead, e.g. for list bullets.
+fn wrap(text: &str, width: usize, first_indent: &str, indent: &str) -> String {
+    let mut out = String::from(first_indent);
+    let mut line_width = first_indent.chars().count();
+    let indent_width = indent.chars().count();
+    let mut line_empty = true;
+
+    for word in style_words(text) {
+        if !line_empty && line_width + 1 + word.width > width {
+            out.push('\n');
+            out.push_str(indent);
+            line_width = indent_width;
+            line_empty = true;
+        }
+        if !line_empty {
+            out.push(' ');
+            line_width += 1;
+        }
+        out.push_str(&word.text);
+        line_width += word.width;
+        line_empty = false;
+    }
+
+    out.push('\n');
+    out
+}
+
+/// Renders puzzle markdown for the terminal, word-wrapped to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut in_block = false;
+    let mut lines = markdown.lines().peekable();
+
+    let flush = |paragraph: &mut Vec<&str>, out: &mut String| {
+        if paragraph.is_empty() {
+            return;
+        }
+        let text = paragraph.join(" ");
+        match text.strip_prefix("* ") {
+            Some(item) => out.push_str(&wrap(item, width, "  • ", "    ")),
+            None => out.push_str(&wrap(&text, width, "", "")),
+        }
+        paragraph.clear();
+    };
+
+    while let Some(line) = lines.next() {
+        if line.trim_start().starts_with("```") {
+            flush(&mut paragraph, &mut out);
+            in_block = !in_block;
+            continue;
+        }
+
+        if in_block {
+            out.push_str(&format!("{CODE_GUTTER}{ANSI_ITALIC}{line}{ANSI_RESET}\n"));
+            continue;
+        }
+
+        // headings are underlined by a line of dashes.
+        if lines
+            .peek()
+            .is_some_and(|next| next.starts_with("---") && next.trim_matches('-').is_empty())
+        {
+            flush(&mut paragraph, &mut out);
+            lines.next();
+            let heading = line.trim_start_matches('\\');
+            out.push_str(&format!("{ANSI_BOLD}{heading}{ANSI_RESET}\n"));
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            flush(&mut paragraph, &mut out);
+            if !out.is_empty() && !out.ends_with("\n\n") {
+                out.push('\n');
+            }
+            continue;
+        }
+
+        // every list item is a paragraph of its own.
+        if line.starts_with("* ") {
+            flush(&mut paragraph, &mut out);
+        }
+        paragraph.push(line.trim());
+    }
+    flush(&mut paragraph, &mut out);
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{code_blocks, expected_answers, highlighted_values, render, title};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const PUZZLE: &str = r#"\--- Day 1: Trebuchet?! ---
+----------
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and `38`. Adding these together produces `*50*`.
+
+\--- Part Two ---
+----------
+
+For example:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces *`310`*.
+"#;
+
+    #[test]
+    fn finds_code_blocks_per_part() {
+        let blocks = code_blocks(PUZZLE);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].part, 1);
+        assert_eq!(blocks[0].content, "1abc2\npqr3stu8vwx\n");
+        assert_eq!(blocks[1].part, 2);
+        assert_eq!(blocks[1].line_count(), 2);
+    }
+
+    #[test]
+    fn finds_expected_answers() {
+        assert_eq!(
+            expected_answers(PUZZLE),
+            [Some("50".to_string()), Some("310".to_string())]
+        );
+        assert_eq!(expected_answers("\\--- Day 2 ---\n"), [None, None]);
+        assert_eq!(title(PUZZLE).as_deref(), Some("Trebuchet?!"));
+        assert_eq!(
+            highlighted_values("`*a*` and `b` and `*c*`, *`d`*"),
+            ["a", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn renders_for_the_terminal() {
+        let rendered = render(PUZZLE, 40);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            format!("{ANSI_BOLD}--- Day 1: Trebuchet?! ---{ANSI_RESET}")
+        );
+        assert_eq!(lines[4], format!("  │ {ANSI_ITALIC}1abc2{ANSI_RESET}"));
+        // paragraphs are wrapped to the width.
+        assert_eq!(lines[7], "In this example, the calibration values");
+        assert_eq!(lines[8], "are 12 and 38. Adding these together");
+        assert_eq!(lines[9], format!("produces {ANSI_BOLD}50{ANSI_RESET}."));
+        assert!(lines
+            .iter()
+            .any(|l| *l == format!("Adding these together produces {ANSI_BOLD}310{ANSI_RESET}.")));
+
+        assert_eq!(
+            render("* a *long* item that wraps\n", 16),
+            format!("  • a {ANSI_BOLD}long{ANSI_RESET} item\n    that wraps\n")
+        );
+    }
+}
@@ -1,8 +1,15 @@
-/// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+//! Module that updates the readme me with timing information.
+//! The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
 
-use crate::Day;
+use crate::template::config::{Column, SortOrder, TableSettings};
+use crate::template::history::{self, Entry};
+use crate::template::{answers, benchmark_chart, config, puzzle};
+use crate::{all_days, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -27,17 +34,31 @@ pub struct Timings {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./{}", config::get().bin_path(day).display())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// The marker that surrounds the table. Workspaces with the year layout get one table per year.
+#[must_use]
+pub fn get_marker() -> String {
+    get_marker_for_year(config::get().namespace_year())
+}
+
+#[must_use]
+pub fn get_marker_for_year(year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,54 +79,375 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Parses a duration as printed by the runner, e.g. `74.1µs`, into nanoseconds.
+/// For possible formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+#[must_use]
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    let parse = |postfix: &str| s.split(postfix).next()?.trim().parse::<f64>().ok();
+
+    match s {
+        s if s.contains("ns") => parse("ns"),
+        s if s.contains("µs") => parse("µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse("ms").map(|x| x * 1_000_000_f64),
+        _ => parse("s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Information about a day that is not part of [`Timings`], shown in the optional columns of the table.
+#[derive(Debug, Default, Clone)]
+pub struct DayDetails {
+    pub title: Option<String>,
+    pub lines: Option<usize>,
+    pub samples: [Option<u128>; 2],
+    /// Time spent in `parse` spans of both parts.
+    pub parse_nanos: Option<f64>,
+    /// Peak heap usage of the hungrier part.
+    pub memory: Option<u64>,
+}
+
+impl DayDetails {
+    /// Collects the details of a day from its answer record, puzzle description and solution file.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn load(day: Day) -> Self {
+        let record = answers::load(day);
+        let parts = [&record.part_1, &record.part_2];
+
+        let parse_times: Vec<f64> = parts
+            .iter()
+            .filter_map(|p| p.parse)
+            .map(|d| d.as_nanos() as f64)
+            .collect();
+
+        Self {
+            title: puzzle::read(day).and_then(|markdown| puzzle::title(&markdown)),
+            lines: fs::read_to_string(config::get().bin_path(day))
+                .ok()
+                .map(|s| s.lines().count()),
+            samples: parts.map(|p| p.timing.map(|(_, samples)| samples)),
+            parse_nanos: (!parse_times.is_empty()).then(|| parse_times.iter().sum()),
+            memory: parts.iter().filter_map(|p| p.memory).max(),
+        }
+    }
+}
+
+/// Everything besides the timings that the table is built from.
+pub struct TableContext<'a> {
+    pub settings: &'a TableSettings,
+    pub history: &'a [Entry],
+    pub details: &'a BTreeMap<Day, DayDetails>,
+    /// Link to the benchmark chart, shown above the table.
+    pub chart: Option<&'a str>,
+}
+
+/// Formats a value with a fixed number of significant figures, e.g. `74.1` or `0.500`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+fn format_significant(value: f64, figures: usize) -> String {
+    let magnitude = if value == 0.0 {
+        0
+    } else {
+        value.abs().log10().floor() as i32
+    };
+    let decimals = (figures as i32 - 1 - magnitude).max(0) as usize;
+    let formatted = format!("{value:.decimals$}");
+
+    // rounding can add a digit, e.g. `0.9996` becomes `1.000`.
+    let rounded = formatted.parse::<f64>().unwrap_or_default().abs();
+    if decimals > 0 && rounded > 0.0 && rounded.log10().floor() as i32 > magnitude {
+        let decimals = decimals - 1;
+        return format!("{value:.decimals$}");
+    }
+    formatted
+}
+
+/// Formats `value` in the largest unit it reaches, moving to the next unit if rounding reaches it.
+fn format_scaled(value: f64, figures: usize, units: &[(&str, f64)]) -> String {
+    let first = units
+        .iter()
+        .rposition(|(_, scale)| value >= *scale)
+        .unwrap_or(0);
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+    for (i, (unit, scale)) in units.iter().enumerate().skip(first) {
+        let formatted = format_significant(value / scale, figures);
+        let next_scale = units.get(i + 1).map(|(_, next)| next / scale);
+        if next_scale.is_none_or(|next| formatted.parse::<f64>().unwrap_or_default() < next) {
+            return format!("{formatted}{unit}");
+        }
+    }
+
+    unreachable!("the last unit is always used")
+}
+
+/// Formats nanoseconds with consistent units, e.g. `74.1µs` or `2.00s`.
+#[must_use]
+pub fn format_nanos(nanos: f64, figures: usize) -> String {
+    format_scaled(
+        nanos,
+        figures,
+        &[("ns", 1.0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)],
+    )
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64, figures: usize) -> String {
+    format_scaled(
+        bytes as f64,
+        figures,
+        &[
+            ("B", 1.0),
+            ("KiB", 1024.0),
+            ("MiB", 1024.0 * 1024.0),
+            ("GiB", 1024.0 * 1024.0 * 1024.0),
+        ],
+    )
+}
+
+/// Reads the rows of an existing table back into [`Timings`].
+/// Columns are located by their headings, so tables with any configured set of columns can be read.
+fn parse_table(table: &str) -> Vec<Timings> {
+    let split = |line: &str| -> Vec<String> {
+        line.trim()
+            .trim_matches('|')
+            .split('|')
+            .map(|cell| cell.trim().to_string())
+            .collect()
+    };
+
+    let headings = table
+        .lines()
+        .filter(|line| line.trim_start().starts_with('|'))
+        .map(split)
+        .find(|cells| cells.iter().any(|c| c == Column::Day.header()))
+        .unwrap_or_default();
+    let column = |column: Column, default: Option<usize>| {
+        headings
+            .iter()
+            .position(|h| h == column.header())
+            .or(if headings.is_empty() { default } else { None })
+    };
+    let day_index = column(Column::Day, Some(0));
+    let (part_1_index, part_2_index) = (
+        column(Column::Part1, Some(1)),
+        column(Column::Part2, Some(2)),
+    );
+    let total_index = column(Column::Total, None);
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let cells = split(line);
+            let day = cells.get(day_index?)?.strip_prefix("[Day ")?;
+            let day = day.split(']').next()?;
+            let day = day.parse::<u8>().ok().and_then(Day::new)?;
+
+            let cell = |index: Option<usize>| {
+                cells
+                    .get(index?)
+                    .map(|c| c.trim_matches('`'))
+                    .filter(|c| *c != "-" && !c.is_empty())
+                    .map(String::from)
+            };
+            let (part_1, part_2) = (cell(part_1_index), cell(part_2_index));
+
+            let part_nanos: Vec<f64> = [&part_1, &part_2]
+                .into_iter()
+                .flatten()
+                .filter_map(|t| parse_duration_nanos(t))
+                .collect();
+            let total_nanos = if part_nanos.is_empty() {
+                cell(total_index)
+                    .and_then(|t| parse_duration_nanos(&t))
+                    .unwrap_or_default()
+            } else {
+                part_nanos.iter().sum()
+            };
+
+            Some(Timings {
+                day,
+                part_1,
+                part_2,
+                total_nanos,
+            })
+        })
+        .collect()
+}
+
+/// Replaces the rows of the days in `updates`, keeping all other rows. The result is sorted by day.
+fn merge_timings(existing: Vec<Timings>, updates: Vec<Timings>) -> Vec<Timings> {
+    let mut merged: BTreeMap<Day, Timings> = existing.into_iter().map(|t| (t.day, t)).collect();
+    for timing in updates {
+        merged.insert(timing.day, timing);
+    }
+    merged.into_values().collect()
+}
+
+fn render_cell(column: Column, timing: &Timings, total_nanos: f64, ctx: &TableContext) -> String {
+    let figures = ctx.settings.significant_figures;
+    let details = ctx.details.get(&timing.day).cloned().unwrap_or_default();
+    let time = |nanos: Option<f64>| {
+        nanos.map_or_else(|| "-".into(), |n| format!("`{}`", format_nanos(n, figures)))
+    };
+    let part = |part: &Option<String>| match part {
+        Some(s) => match parse_duration_nanos(s) {
+            Some(nanos) => time(Some(nanos)),
+            None => format!("`{s}`"),
+        },
+        None => "`-`".into(),
+    };
+
+    match column {
+        Column::Day => format!(
+            "[Day {}]({})",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            get_path_for_bin(timing.day)
+        ),
+        Column::Title => details.title.unwrap_or_else(|| "-".into()),
+        Column::Part1 => part(&timing.part_1),
+        Column::Part2 => part(&timing.part_2),
+        Column::Total => time(Some(timing.total_nanos)),
+        Column::Parse => time(details.parse_nanos),
+        Column::Samples => {
+            let [a, b] = details
+                .samples
+                .map(|s| s.map_or_else(|| "-".into(), |s| s.to_string()));
+            format!("{a} / {b}")
+        }
+        Column::Share if total_nanos > 0.0 => format!(
+            "{}%",
+            format_significant(timing.total_nanos / total_nanos * 100.0, figures)
+        ),
+        Column::Share => "-".into(),
+        Column::Memory => details
+            .memory
+            .map_or_else(|| "-".into(), |m| format!("`{}`", format_bytes(m, figures))),
+        Column::Lines => details.lines.map_or_else(|| "-".into(), |l| l.to_string()),
+        Column::Trend => history::trend(ctx.history, timing.day),
+    }
+}
+
+fn construct_table(prefix: &str, marker: &str, timings: &[Timings], ctx: &TableContext) -> String {
+    let header = format!("{prefix} Benchmarks");
+    let columns = &ctx.settings.columns;
+    let total_nanos: f64 = timings.iter().map(|t| t.total_nanos).sum();
+
+    let mut lines: Vec<String> = vec![marker.into(), header, String::new()];
+
+    if let Some(chart) = ctx.chart {
+        lines.push(format!("![Benchmark chart]({chart})"));
+        lines.push(String::new());
+    }
+
+    let headings: Vec<&str> = columns.iter().map(|c| c.header()).collect();
+    lines.push(format!("| {} |", headings.join(" | ")));
+    lines.push(format!("|{}", " :---: |".repeat(columns.len())));
+
+    let mut rows: Vec<&Timings> = timings.iter().collect();
+    if ctx.settings.sort == SortOrder::Time {
+        rows.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    for timing in rows {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|&column| render_cell(column, timing, total_nanos, ctx))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!(
+        "**Total: {}**",
+        format_nanos(total_nanos, ctx.settings.significant_figures)
+    ));
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Merges `timings` into the table between the markers and returns the rows of the merged table.
+/// The total is computed from all rows.
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    ctx: &TableContext,
+) -> Result<Vec<Timings>, Error> {
+    let marker = get_marker();
+    let positions = locate_table(s, &marker)?;
+    let existing = parse_table(&s[positions.pos_start..positions.pos_end]);
+    let timings = merge_timings(existing, timings);
+
+    let prefix = match config::get().namespace_year() {
+        Some(year) => format!("## {year}"),
+        None => "##".into(),
+    };
+    let table = construct_table(&prefix, &marker, &timings, ctx);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+    Ok(timings)
+}
+
+/// Link to `path` relative to the folder of the readme, e.g. `./data/benchmarks/chart.svg`.
+fn relative_link(readme: &Path, path: &Path) -> String {
+    let readme_dir = readme.parent().unwrap_or(Path::new(""));
+
+    let link = match path.strip_prefix(readme_dir) {
+        Ok(rest) => Path::new(".").join(rest),
+        Err(_) => readme_dir
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .map(|_| Path::new(".."))
+            .collect::<PathBuf>()
+            .join(path),
+    };
+
+    link.to_string_lossy().replace('\\', "/")
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let path = "README.md";
+pub fn update(timings: Vec<Timings>) -> Result<(), Error> {
+    let path = &config::get().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+
+    let chart_path = config::get().chart_path();
+    let chart_link = relative_link(path, &chart_path);
+    let details = all_days().map(|day| (day, DayDetails::load(day))).collect();
+    let ctx = TableContext {
+        settings: &config::get().table,
+        history: &history::load(),
+        details: &details,
+        chart: Some(&chart_link),
+    };
+    let timings = update_content(&mut readme, timings, &ctx)?;
+
+    if let Some(parent) = chart_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&chart_path, benchmark_chart::render(&timings))?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use std::{collections::BTreeMap, path::Path};
+
+    use super::{
+        format_bytes, format_nanos, parse_duration_nanos, relative_link, update_content,
+        DayDetails, Error, TableContext, Timings, MARKER,
+    };
     use crate::day;
+    use crate::template::config::{Column, SortOrder, TableSettings};
+
+    fn update_default(s: &mut String, timings: Vec<Timings>) -> Result<Vec<Timings>, Error> {
+        let ctx = TableContext {
+            settings: &TableSettings::default(),
+            history: &[],
+            details: &BTreeMap::new(),
+            chart: None,
+        };
+        update_content(s, timings, &ctx)
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -113,19 +455,19 @@ mod tests {
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
+                total_nanos: 3e+7,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
+                total_nanos: 7e+7,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
+                total_nanos: 9e+7,
             },
         ]
     }
@@ -134,28 +476,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,24 +505,198 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_default(&mut s, get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Part 1 | Part 2 | Trend |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | - / - |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | - / - |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` | - / - |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 190ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn merges_with_existing_rows() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_default(&mut s, get_mock_timings()).unwrap();
+
+        let rerun = vec![
+            Timings {
+                day: day!(2),
+                part_1: Some("5ms".into()),
+                part_2: None,
+                total_nanos: 5e+6,
+            },
+            Timings {
+                day: day!(3),
+                part_1: Some("1.5µs".into()),
+                part_2: Some("500ns".into()),
+                total_nanos: 2000.0,
+            },
+        ];
+        update_default(&mut s, rerun).unwrap();
+
+        let rows: Vec<&str> = s.lines().filter(|l| l.starts_with("| [Day")).collect();
+        assert_eq!(
+            rows,
+            [
+                "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | - / - |",
+                "| [Day 2](./src/bin/02.rs) | `5.00ms` | `-` | - / - |",
+                "| [Day 3](./src/bin/03.rs) | `1.50µs` | `500ns` | - / - |",
+                "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` | - / - |",
+            ]
+        );
+        assert!(s.contains("**Total: 125ms**"));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration_nanos("500ns"), Some(500.0));
+        assert_eq!(parse_duration_nanos("1.5µs"), Some(1500.0));
+        assert_eq!(parse_duration_nanos("10ms"), Some(10_000_000.0));
+        assert_eq!(parse_duration_nanos("1.2s"), Some(1_200_000_000.0));
+        assert_eq!(parse_duration_nanos("-"), None);
+    }
+
+    #[test]
+    fn links_chart() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let ctx = TableContext {
+            settings: &TableSettings::default(),
+            history: &[],
+            details: &BTreeMap::new(),
+            chart: Some("./chart.svg"),
+        };
+        update_content(&mut s, get_mock_timings(), &ctx).unwrap();
+        assert!(s.contains("## Benchmarks\n\n![Benchmark chart](./chart.svg)\n\n| Day |"));
+
+        assert_eq!(
+            relative_link(
+                Path::new("README.md"),
+                Path::new("data/benchmarks/chart.svg")
+            ),
+            "./data/benchmarks/chart.svg"
+        );
+        assert_eq!(
+            relative_link(Path::new("docs/README.md"), Path::new("docs/chart.svg")),
+            "./chart.svg"
+        );
+        assert_eq!(
+            relative_link(Path::new("docs/README.md"), Path::new("data/chart.svg")),
+            "../data/chart.svg"
+        );
+    }
+
+    #[test]
+    fn renders_configured_columns() {
+        let settings = TableSettings {
+            columns: vec![
+                Column::Day,
+                Column::Title,
+                Column::Total,
+                Column::Parse,
+                Column::Samples,
+                Column::Share,
+                Column::Memory,
+                Column::Lines,
+            ],
+            sort: SortOrder::Time,
+            significant_figures: 2,
+        };
+        let details = BTreeMap::from([(
+            day!(2),
+            DayDetails {
+                title: Some("Cube Conundrum".into()),
+                lines: Some(42),
+                samples: [Some(100), None],
+                parse_nanos: Some(1234.0),
+                memory: Some(3 * 1024 * 1024),
+            },
+        )]);
+        let ctx = TableContext {
+            settings: &settings,
+            history: &[],
+            details: &details,
+            chart: None,
+        };
+
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), &ctx).unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[4],
+            "| Day | Title | Total | Parse | Samples | Share | Memory | Lines |"
+        );
+        // slowest days first.
+        assert_eq!(
+            lines[6],
+            "| [Day 4](./src/bin/04.rs) | - | `90ms` | - | - / - | 47% | - | - |"
+        );
+        assert_eq!(
+            lines[7],
+            "| [Day 2](./src/bin/02.rs) | Cube Conundrum | `70ms` | `1.2µs` | 100 / - | 37% | `3.0MiB` | 42 |"
+        );
+        assert!(s.contains("**Total: 190ms**"));
+
+        // rows without part columns are read back from the total column.
+        update_content(&mut s, vec![], &ctx).unwrap();
+        assert!(s.contains("**Total: 190ms**"));
+    }
+
+    #[test]
+    fn merges_rows_of_reordered_columns() {
+        let settings = TableSettings {
+            columns: vec![Column::Part1, Column::Day, Column::Part2],
+            ..TableSettings::default()
+        };
+        let ctx = TableContext {
+            settings: &settings,
+            history: &[],
+            details: &BTreeMap::new(),
+            chart: None,
+        };
+
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), &ctx).unwrap();
+        let rerun = vec![Timings {
+            day: day!(2),
+            part_1: Some("5ms".into()),
+            part_2: None,
+            total_nanos: 5e+6,
+        }];
+        update_content(&mut s, rerun, &ctx).unwrap();
+
+        let rows: Vec<&str> = s.lines().filter(|l| l.contains("[Day")).collect();
+        assert_eq!(
+            rows,
+            [
+                "| `10.0ms` | [Day 1](./src/bin/01.rs) | `20.0ms` |",
+                "| `5.00ms` | [Day 2](./src/bin/02.rs) | `-` |",
+                "| `40.0ms` | [Day 4](./src/bin/04.rs) | `50.0ms` |",
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_with_significant_figures() {
+        assert_eq!(format_nanos(74_130.0, 3), "74.1µs");
+        assert_eq!(format_nanos(2e9, 3), "2.00s");
+        assert_eq!(format_nanos(999_960.0, 3), "1.00ms");
+        assert_eq!(format_nanos(0.0, 2), "0.0ns");
+        assert_eq!(format_nanos(1.5e11, 2), "150s");
+        assert_eq!(format_bytes(512, 3), "512B");
+        assert_eq!(format_bytes(1536, 2), "1.5KiB");
+    }
 }
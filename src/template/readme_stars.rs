@@ -0,0 +1,151 @@
+//! Module that fills the `advent_readme_stars` table of the readme from the local answer records.
+//! The table has the same shape as the one rendered by the `advent-readme-stars` action, without a network request.
+use std::{fs, path::Path};
+
+use crate::template::answers::{self, DayRecord};
+use crate::template::config;
+use crate::template::readme_benchmarks::{get_path_for_bin, locate_table, Error};
+use crate::{all_days, Day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// A row of the stars table.
+pub struct StarRow {
+    pub day: Day,
+    /// Whether the website accepted the answers of part one and part two.
+    pub stars: [bool; 2],
+    /// Path of the solution file, if it exists.
+    pub solution: Option<String>,
+}
+
+impl StarRow {
+    pub fn new(day: Day, record: &DayRecord, solution: Option<String>) -> Self {
+        Self {
+            day,
+            stars: [record.part_1.solved, record.part_2.solved],
+            solution,
+        }
+    }
+}
+
+/// The marker that surrounds the table. Workspaces with the year layout get one table per year.
+#[must_use]
+pub fn get_marker() -> String {
+    match config::get().namespace_year() {
+        Some(year) => format!("<!--- advent_readme_stars table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
+fn construct_table(marker: &str, year: u16, rows: &[StarRow]) -> String {
+    let mut lines: Vec<String> = vec![
+        marker.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 | Solution |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for row in rows {
+        let star = |solved: bool| if solved { "⭐" } else { " " };
+        let solution = row.solution.as_ref().map_or_else(
+            || "-".into(),
+            |path| {
+                let file_name = path.rsplit('/').next().unwrap_or(path);
+                format!("[{file_name}]({path})")
+            },
+        );
+
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} | {solution} |",
+            star(row.stars[0]),
+            star(row.stars[1]),
+            day = row.day.into_inner(),
+        ));
+    }
+
+    let stars: usize = rows.iter().flat_map(|r| r.stars).filter(|s| *s).count();
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}/50**"));
+    lines.push(marker.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, rows: &[StarRow]) -> Result<(), Error> {
+    let marker = get_marker();
+    let positions = locate_table(s, &marker)?;
+    let table = construct_table(&marker, year, rows);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Rows for all days that have a solution file or an accepted answer.
+pub fn collect_rows() -> Vec<StarRow> {
+    all_days()
+        .filter_map(|day| {
+            let path = get_path_for_bin(day);
+            let solution = Path::new(&path).exists().then_some(path);
+
+            let row = StarRow::new(day, &answers::load(day), solution);
+            (row.solution.is_some() || row.stars.contains(&true)).then_some(row)
+        })
+        .collect()
+}
+
+pub fn update(year: u16, rows: &[StarRow]) -> Result<(), Error> {
+    let path = &config::get().readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, rows)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, StarRow, MARKER};
+    use crate::day;
+
+    fn get_mock_rows() -> Vec<StarRow> {
+        vec![
+            StarRow {
+                day: day!(1),
+                stars: [true, true],
+                solution: Some("./src/bin/01.rs".into()),
+            },
+            StarRow {
+                day: day!(2),
+                stars: [true, false],
+                solution: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{MARKER}\n\nbar");
+        update_content(&mut s, 2023, &get_mock_rows()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 | Solution |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ | [01.rs](./src/bin/01.rs) |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   | - |",
+            "",
+            "**Stars: 3/50**",
+            "<!--- advent_readme_stars table --->",
+            "",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+
+        // a second run replaces the table between the markers.
+        update_content(&mut s, 2023, &get_mock_rows()[..1]).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.contains("**Stars: 2/50**"));
+    }
+}
@@ -0,0 +1,21 @@
+//! Solutions compiled into the library, which lets `cargo all` run every day in a single process.
+//! The build script includes each file in `src/bin` as a module; the `solution!` macro of these modules
+//! expands to an unused `main`. Library tests leave the solutions out, they are tested through their binaries.
+use crate::template::runner::PartRun;
+use crate::Day;
+
+/// A solution that can be run in-process.
+pub struct Solution {
+    /// The name of the solution binary, e.g. `01` or `2023-01`.
+    pub name: &'static str,
+    pub day: Day,
+    /// Run and print part one and part two for an input, like the binary of the solution does.
+    pub parts: [fn(&str) -> PartRun; 2],
+}
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Looks up the solution of a binary.
+pub fn find(name: &str) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.name == name)
+}
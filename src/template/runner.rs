@@ -1,33 +1,266 @@
-/// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+//! Encapsulates code that interacts with solution functions.
+use crate::template::aoc_client::SubmitVerdict;
+use crate::template::config::{self, BenchSettings};
+use crate::template::{
+    answers, aoc_client, history, memory, profiler, puzzle, ANSI_ITALIC, ANSI_RESET,
+};
 use crate::Day;
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, panic, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+thread_local! {
+    /// The output of the runner on this thread while it is captured, see [`capture_output`].
+    static CAPTURED: RefCell<Option<CapturedOutput>> = const { RefCell::new(None) };
+}
+
+/// What the runner printed on a thread while its output was captured.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CapturedOutput {
+    pub stdout: String,
+    /// Errors of the runner and messages of panics.
+    pub stderr: String,
+}
+
+/// Like `print!`, but appends to the captured output of the thread if there is any.
+macro_rules! output {
+    ($($arg:tt)*) => {
+        write_output(&format!($($arg)*))
+    };
+}
+
+macro_rules! outputln {
+    () => {
+        write_output("\n")
+    };
+    ($($arg:tt)*) => {
+        write_output(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Like `eprintln!`, but appends to the captured errors of the thread if there are any.
+macro_rules! errorln {
+    ($($arg:tt)*) => {
+        write_error(&format!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+fn write_output(s: &str) {
+    let is_captured = CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(captured) => {
+            captured.stdout.push_str(s);
+            true
+        }
+        None => false,
+    });
+
+    if !is_captured {
+        print!("{s}");
+    }
+}
+
+/// Like `eprint!`, but appends to the captured errors of the thread if there are any.
+pub fn write_error(s: &str) {
+    let is_captured = CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(captured) => {
+            captured.stderr.push_str(s);
+            true
+        }
+        None => false,
+    });
+
+    if !is_captured {
+        eprint!("{s}");
+    }
+}
+
+/// Buffers the results and errors printed by [`run_part`] on this thread, including the messages
+/// of panics, instead of writing them to stdout and stderr.
+/// Used to run several days at once without interleaving their output.
+pub fn capture_output() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CAPTURED.with_borrow(Option::is_none) {
+                default_hook(info);
+                return;
+            }
+
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>");
+            let location = info
+                .location()
+                .map(|l| format!(" at {}:{}:{}", l.file(), l.line(), l.column()))
+                .unwrap_or_default();
+            let thread = thread::current();
+            let name = thread.name().unwrap_or("<unnamed>");
+
+            write_error(&format!("thread '{name}' panicked{location}:\n{message}\n"));
+        }));
+    });
+
+    CAPTURED.with_borrow_mut(|captured| *captured = Some(CapturedOutput::default()));
+}
+
+/// Stops capturing and returns the output buffered since [`capture_output`].
+pub fn take_output() -> CapturedOutput {
+    CAPTURED.with_borrow_mut(Option::take).unwrap_or_default()
+}
+
+/// The outcome of a part, as printed by [`run_part`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> PartRun {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let bench_settings = config::get().bench_for(day);
+
+    // timed runs profile the first execution to measure the time spent in `parse` spans.
+    if is_profiled() || is_timed() {
+        profiler::enable();
+    }
+
+    // the part itself is the root of the span tree.
+    let root_span = if part == 1 { "part_one" } else { "part_two" };
+    let func = |input: I| {
+        crate::span!(root_span);
+        func(input)
+    };
+
+    let baseline = memory::reset_peak();
+    let peak_memory = Cell::new(None);
+
+    let (result, duration, samples) = run_timed(func, input, &bench_settings, |result| {
+        peak_memory.set(memory::peak_since(baseline));
+        if !is_profiled() {
+            profiler::disable();
+        }
+        print_result(result, &part_str, "");
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    let report = (is_profiled() || is_timed()).then(profiler::take_report);
+
+    if let Some(report) = report.as_ref().filter(|_| is_profiled()) {
+        print_profile(report);
+    }
+
+    let run = answers::PartRecord {
+        answer: result.as_ref().map(T::to_string),
+        timing: is_timed().then_some((duration, samples)),
+        parse: report.and_then(|r| r.per_call("parse")),
+        memory: peak_memory.get(),
+        solved: false,
+        accepted: None,
+    };
+
+    let outcome = PartRun {
+        answer: run.answer.clone(),
+        duration,
+        samples,
+    };
+
+    if let Err(e) = answers::record(day, part, run) {
+        errorln!("Could not record result of part {part}: {e}");
+    }
+
+    if is_timed() && result.is_some() {
+        if let Err(e) = history::append(day, part, duration) {
+            errorln!("Could not append timing of part {part} to the history: {e}");
+        }
+    }
+
     if let Some(result) = result {
-        submit_result(result, day, part);
+        match submit_result(result, day, part) {
+            Some(Ok(verdict)) => {
+                println!("{ANSI_BOLD}{verdict}{ANSI_RESET}");
+                if verdict.is_failure() {
+                    process::exit(1);
+                }
+                let accepted = (verdict == SubmitVerdict::Correct)
+                    .then_some(outcome.answer.as_deref())
+                    .flatten();
+                if let Err(e) = answers::mark_solved(day, part, accepted) {
+                    eprintln!("Could not record submission of part {part}: {e}");
+                }
+                if should_refresh_puzzle(part, verdict) {
+                    refresh_puzzle(day);
+                }
+            }
+            Some(Err(e)) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+            None => {}
+        }
+    }
+
+    outcome
+}
+
+/// Part two is only revealed once part one is solved, so the description is fetched again then.
+fn should_refresh_puzzle(part: u8, verdict: SubmitVerdict) -> bool {
+    part == 1 && verdict == SubmitVerdict::Correct
+}
+
+/// Re-fetches the puzzle description after part one was solved, as it now contains part two.
+/// The input is left untouched.
+fn refresh_puzzle(day: Day) {
+    println!("Fetching part two...");
+
+    if let Err(e) = aoc_client::get().refresh_puzzle(day) {
+        eprintln!("Could not refresh the puzzle description: {e}");
+        eprintln!("Run \"cargo download {day}\" to fetch part two.");
+        return;
+    }
+
+    let [_, part_two] = puzzle::read(day)
+        .map(|markdown| puzzle::sections(&markdown))
+        .unwrap_or_default();
+
+    match part_two.lines().next() {
+        Some(heading) => {
+            println!();
+            println!(
+                "{ANSI_BOLD}{}{ANSI_RESET}",
+                heading.trim_start_matches('\\')
+            );
+            println!("Run \"cargo read {day} --part 2\" to read it.");
+        }
+        None => eprintln!("The refreshed puzzle description does not contain part two yet."),
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Both the target time and the sample bounds can be adjusted in `aoc.toml`.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    settings: &BenchSettings,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
@@ -36,8 +269,8 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let run = if is_timed() {
+        bench(func, input, &base_time, settings)
     } else {
         (base_time, 1)
     };
@@ -45,19 +278,27 @@ fn run_timed<I: Clone, T>(
     (result, run.0, run.1)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+fn is_profiled() -> bool {
+    env::args().any(|x| x == "--profile")
+}
+
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    settings: &BenchSettings,
+) -> (Duration, u128) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    output!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations = (settings.target.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(settings.min_samples, settings.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -100,41 +341,56 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    output!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    output!("\r");
+                    outputln!("{str}");
+                    outputln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    output!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    output!("\r");
+                    outputln!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                output!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                output!("\r");
+                outputln!("{part}: ✖             ");
             }
         }
     }
 }
 
+fn print_profile(report: &profiler::Report) {
+    if report.is_empty() {
+        return;
+    }
+
+    outputln!(
+        "{ANSI_ITALIC}{:<32} {:>12} {:>7} {:>9}{ANSI_RESET}",
+        "span",
+        "total",
+        "share",
+        "calls"
+    );
+    output!("{report}");
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the configured client is usable, e.g. aoc-cli is installed.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmitVerdict, aoc_client::Error>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +413,53 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let client = aoc_client::get();
+
+    if let Err(e) = client.check() {
+        eprintln!("{e}");
+        eprintln!("Run \"cargo doctor\" to check your setup.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    Some(client.submit(day, part, &result.to_string()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{panic, time::Duration};
+
+    use super::{capture_output, should_refresh_puzzle, take_output, write_error};
+    use crate::template::aoc_client::SubmitVerdict;
+
+    #[test]
+    fn captures_errors_and_panics() {
+        capture_output();
+        write_error("Could not record result of part 1\n");
+        let result = panic::catch_unwind(|| panic!("no solution"));
+        let captured = take_output();
+
+        assert!(result.is_err());
+        assert!(captured.stdout.is_empty());
+        assert!(captured
+            .stderr
+            .starts_with("Could not record result of part 1\nthread '"));
+        assert!(captured.stderr.ends_with(":\nno solution\n"));
+    }
+
+    #[test]
+    fn refreshes_the_puzzle_after_solving_part_one() {
+        assert!(should_refresh_puzzle(1, SubmitVerdict::Correct));
+        assert!(!should_refresh_puzzle(2, SubmitVerdict::Correct));
+
+        for verdict in [
+            SubmitVerdict::TooHigh,
+            SubmitVerdict::TooLow,
+            SubmitVerdict::Wrong,
+            SubmitVerdict::RateLimited(Duration::from_secs(42)),
+            SubmitVerdict::AlreadySolved,
+        ] {
+            assert!(!should_refresh_puzzle(1, verdict), "{verdict:?}");
+        }
+    }
 }
@@ -0,0 +1,215 @@
+//! Locates the Advent of Code session cookie in the same places as aoc-cli.
+//!
+//! Besides the default session file, cookies can be stored as named profiles in
+//! `~/.config/adventofcode/profiles/<name>.session`. The active profile is chosen with the
+//! `AOC_PROFILE` environment variable or `cargo session switch`, and the `AOC_SESSION`
+//! environment variable overrides every file, e.g. for CI.
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+pub const SESSION_FILE: &str = ".adventofcode.session";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+
+/// Where the cookie in use comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Env,
+    File(PathBuf),
+}
+
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn config_dir() -> Option<PathBuf> {
+    Some(home_dir()?.join(".config").join("adventofcode"))
+}
+
+fn profiles_dir() -> Option<PathBuf> {
+    Some(config_dir()?.join("profiles"))
+}
+
+fn active_profile_file() -> Option<PathBuf> {
+    Some(config_dir()?.join("active_profile"))
+}
+
+/// Possible locations of the session file: the home directory first, then the config directory.
+pub fn session_file_candidates() -> Vec<PathBuf> {
+    home_dir()
+        .map(|home| {
+            vec![
+                home.join(SESSION_FILE),
+                home.join(".config").join("adventofcode.session"),
+            ]
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the first existing session file.
+pub fn find_session_file() -> Option<PathBuf> {
+    session_file_candidates().into_iter().find(|p| p.exists())
+}
+
+/// Profile names end up in paths, so they are restricted to a safe set of characters.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "default"
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+pub fn profile_file(name: &str) -> Option<PathBuf> {
+    Some(profiles_dir()?.join(format!("{name}.session")))
+}
+
+/// Names of all stored profiles, sorted.
+pub fn profiles() -> Vec<String> {
+    let Some(entries) = profiles_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == "session")
+                .then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// The active profile, [`None`] for the default session file.
+pub fn active_profile() -> Option<String> {
+    let name = match env::var(PROFILE_ENV) {
+        Ok(name) => name,
+        Err(_) => fs::read_to_string(active_profile_file()?).ok()?,
+    };
+    let name = name.trim();
+    is_valid_profile_name(name).then(|| name.to_string())
+}
+
+/// Writes a file that only the current user can read, as it may contain a session cookie.
+/// Permissions of an existing file are tightened as well.
+pub fn write_private(path: &Path, contents: &str) -> Result<(), io::Error> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+/// Makes `name` the active profile, or switches back to the default session file for [`None`].
+pub fn set_active_profile(name: Option<&str>) -> Result<(), io::Error> {
+    let path = active_profile_file().ok_or(io::ErrorKind::NotFound)?;
+    match name {
+        Some(name) => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_private(&path, name)
+        }
+        None if path.exists() => fs::remove_file(path),
+        None => Ok(()),
+    }
+}
+
+/// The file holding the cookie of the active profile.
+pub fn active_session_file() -> Option<PathBuf> {
+    match active_profile() {
+        Some(name) => profile_file(&name),
+        None => find_session_file(),
+    }
+}
+
+/// The file `cargo session set` writes to for a profile.
+pub fn writable_session_file(profile: Option<&str>) -> Option<PathBuf> {
+    match profile {
+        Some(name) => profile_file(name),
+        None => find_session_file().or_else(|| session_file_candidates().into_iter().next()),
+    }
+}
+
+/// Reads the cookie in use and where it comes from.
+pub fn read_session_with_source() -> Option<(String, Source)> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        let session = session.trim();
+        if !session.is_empty() {
+            return Some((session.to_string(), Source::Env));
+        }
+    }
+
+    let path = active_session_file()?;
+    let contents = fs::read_to_string(&path).ok()?;
+    let session = contents.trim();
+    (!session.is_empty()).then(|| (session.to_string(), Source::File(path)))
+}
+
+/// Reads the session cookie, returning [`None`] if there is no non-empty session file.
+pub fn read_session() -> Option<String> {
+    read_session_with_source().map(|(session, _)| session)
+}
+
+/// Hides all but the first and last four characters of a cookie.
+pub fn mask(session: &str) -> String {
+    let chars: Vec<char> = session.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(chars.len());
+    }
+
+    let start: String = chars[..4].iter().collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{start}{}{end}", "*".repeat(chars.len() - 8))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_valid_profile_name, mask, write_private};
+
+    #[test]
+    fn masks_sessions() {
+        assert_eq!(mask("53616c7465645f5f"), "5361********5f5f");
+        assert_eq!(mask("short"), "*****");
+    }
+
+    #[test]
+    fn validates_profile_names() {
+        assert!(is_valid_profile_name("work-2"));
+        assert!(!is_valid_profile_name("default"));
+        assert!(!is_valid_profile_name("../home"));
+        assert!(!is_valid_profile_name(""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writes_private_files() {
+        use std::{env, fs, os::unix::fs::PermissionsExt, process};
+
+        let path = env::temp_dir().join(format!("aoc-private-{}.session", process::id()));
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&path, "53616c7465645f5f").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "53616c7465645f5f");
+        fs::remove_file(&path).unwrap();
+    }
+}
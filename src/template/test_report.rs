@@ -0,0 +1,333 @@
+//! Renders the results of `cargo all` as test reports for CI, as JUnit XML or TAP.
+//! Every part of a day becomes a test case.
+use std::{
+    fmt::{Display, Write},
+    fs, io,
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    Invalid(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Invalid(spec) => write!(
+                f,
+                "invalid report \"{spec}\", expected junit=<path>, tap or tap=<path>"
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(e) => Some(e),
+            Error::Invalid(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Junit,
+    Tap,
+}
+
+/// A report requested with `--report`, e.g. `junit=target/aoc.xml` or `tap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub format: Format,
+    /// Where to write the report. TAP reports without a path are printed to stdout.
+    pub path: Option<PathBuf>,
+}
+
+impl FromStr for Report {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (format, path) = match s.split_once('=') {
+            Some((format, path)) if !path.is_empty() => (format, Some(PathBuf::from(path))),
+            Some(_) => return Err(Error::Invalid(s.into())),
+            None => (s, None),
+        };
+
+        match (format.to_ascii_lowercase().as_str(), path) {
+            ("junit", Some(path)) => Ok(Self {
+                format: Format::Junit,
+                path: Some(path),
+            }),
+            ("tap", path) => Ok(Self {
+                format: Format::Tap,
+                path,
+            }),
+            _ => Err(Error::Invalid(s.into())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// Contains the reason, e.g. `returned None`.
+    Failed(String),
+    /// Contains the reason, e.g. `not scaffolded`.
+    Skipped(String),
+}
+
+/// The result of one part of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration: Option<Duration>,
+    pub outcome: Outcome,
+}
+
+impl TestCase {
+    fn name(&self) -> String {
+        format!("day {} part {}", self.day, self.part)
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn seconds(duration: Option<Duration>) -> String {
+    format!("{:.6}", duration.unwrap_or_default().as_secs_f64())
+}
+
+fn render_junit(cases: &[TestCase], year: Option<u16>) -> String {
+    let count = |f: fn(&Outcome) -> bool| cases.iter().filter(|c| f(&c.outcome)).count();
+    let failures = count(|o| matches!(o, Outcome::Failed(_)));
+    let skipped = count(|o| matches!(o, Outcome::Skipped(_)));
+    let time = seconds(Some(cases.iter().filter_map(|c| c.duration).sum()));
+    let name = match year {
+        Some(year) => format!("Advent of Code {year}"),
+        None => "Advent of Code".into(),
+    };
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let totals = format!(
+        "tests=\"{}\" failures=\"{failures}\" errors=\"0\" skipped=\"{skipped}\" time=\"{time}\"",
+        cases.len()
+    );
+    let _ = writeln!(out, "<testsuites name=\"advent_of_code\" {totals}>");
+    let _ = writeln!(out, "  <testsuite name=\"{name}\" {totals}>");
+
+    for case in cases {
+        let _ = writeln!(
+            out,
+            "    <testcase classname=\"day_{}\" name=\"part {}\" time=\"{}\">",
+            case.day,
+            case.part,
+            seconds(case.duration)
+        );
+
+        match &case.outcome {
+            Outcome::Passed => {}
+            Outcome::Failed(message) => {
+                let _ = writeln!(out, "      <failure message=\"{}\"/>", escape_xml(message));
+            }
+            Outcome::Skipped(message) => {
+                let _ = writeln!(out, "      <skipped message=\"{}\"/>", escape_xml(message));
+            }
+        }
+        if let Some(answer) = &case.answer {
+            let _ = writeln!(out, "      <system-out>{}</system-out>", escape_xml(answer));
+        }
+        out.push_str("    </testcase>\n");
+    }
+
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    out
+}
+
+/// Quotes a value for the YAML block of a TAP test point.
+fn yaml_string(s: &str) -> String {
+    format!(
+        "\"{}\"",
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+fn render_tap(cases: &[TestCase]) -> String {
+    let mut out = format!("TAP version 13\n1..{}\n", cases.len());
+
+    for (i, case) in cases.iter().enumerate() {
+        let number = i + 1;
+        let name = case.name();
+        match &case.outcome {
+            Outcome::Skipped(message) => {
+                let _ = writeln!(out, "ok {number} - {name} # SKIP {message}");
+                continue;
+            }
+            Outcome::Passed => {
+                let _ = writeln!(out, "ok {number} - {name}");
+            }
+            Outcome::Failed(_) => {
+                let _ = writeln!(out, "not ok {number} - {name}");
+            }
+        }
+
+        out.push_str("  ---\n");
+        if let Outcome::Failed(message) = &case.outcome {
+            let _ = writeln!(out, "  message: {}", yaml_string(message));
+        }
+        if let Some(answer) = &case.answer {
+            let _ = writeln!(out, "  answer: {}", yaml_string(answer));
+        }
+        if let Some(duration) = case.duration {
+            let _ = writeln!(out, "  duration_ms: {:.3}", duration.as_secs_f64() * 1000.0);
+        }
+        out.push_str("  ...\n");
+    }
+
+    out
+}
+
+pub fn render(format: Format, cases: &[TestCase], year: Option<u16>) -> String {
+    match format {
+        Format::Junit => render_junit(cases, year),
+        Format::Tap => render_tap(cases),
+    }
+}
+
+/// Writes a report to its path, creating parent directories as needed, or prints it to stdout.
+pub fn write(report: &Report, cases: &[TestCase], year: Option<u16>) -> Result<(), Error> {
+    let content = render(report.format, cases, year);
+
+    let Some(path) = &report.path else {
+        print!("{content}");
+        return Ok(());
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{render, Format, Outcome, Report, TestCase};
+    use crate::day;
+
+    fn get_mock_cases() -> Vec<TestCase> {
+        vec![
+            TestCase {
+                day: day!(1),
+                part: 1,
+                answer: Some("142".into()),
+                duration: Some(Duration::from_micros(74)),
+                outcome: Outcome::Passed,
+            },
+            TestCase {
+                day: day!(1),
+                part: 2,
+                answer: Some("<281>".into()),
+                duration: Some(Duration::from_micros(26)),
+                outcome: Outcome::Failed("returned <281>, expected 280".into()),
+            },
+            TestCase {
+                day: day!(2),
+                part: 1,
+                answer: None,
+                duration: None,
+                outcome: Outcome::Skipped("not scaffolded".into()),
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_reports() {
+        assert_eq!(
+            "junit=target/aoc.xml".parse::<Report>().unwrap(),
+            Report {
+                format: Format::Junit,
+                path: Some(PathBuf::from("target/aoc.xml")),
+            }
+        );
+        assert_eq!(
+            "tap".parse::<Report>().unwrap(),
+            Report {
+                format: Format::Tap,
+                path: None,
+            }
+        );
+        assert_eq!(
+            "TAP=target/aoc.tap".parse::<Report>().unwrap(),
+            Report {
+                format: Format::Tap,
+                path: Some(PathBuf::from("target/aoc.tap")),
+            }
+        );
+        assert!("junit".parse::<Report>().is_err());
+        assert!("tap=".parse::<Report>().is_err());
+        assert!("xml=a.xml".parse::<Report>().is_err());
+    }
+
+    #[test]
+    fn renders_junit() {
+        let xml = render(Format::Junit, &get_mock_cases(), Some(2023));
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites"));
+        assert!(xml.contains(
+            "<testsuite name=\"Advent of Code 2023\" tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\" time=\"0.000100\">"
+        ));
+        assert!(xml.contains(
+            "<testcase classname=\"day_01\" name=\"part 2\" time=\"0.000026\">\n      <failure message=\"returned &lt;281&gt;, expected 280\"/>\n      <system-out>&lt;281&gt;</system-out>\n    </testcase>"
+        ));
+        assert!(xml.contains("<skipped message=\"not scaffolded\"/>"));
+        assert!(xml.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn renders_tap() {
+        let tap = render(Format::Tap, &get_mock_cases(), None);
+        let expected = [
+            "TAP version 13",
+            "1..3",
+            "ok 1 - day 01 part 1",
+            "  ---",
+            "  answer: \"142\"",
+            "  duration_ms: 0.074",
+            "  ...",
+            "not ok 2 - day 01 part 2",
+            "  ---",
+            "  message: \"returned <281>, expected 280\"",
+            "  answer: \"<281>\"",
+            "  duration_ms: 0.026",
+            "  ...",
+            "ok 3 - day 02 part 1 # SKIP not scaffolded",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(tap, expected);
+    }
+}
@@ -0,0 +1,341 @@
+//! A tiny parser and writer for the subset of TOML used by the template's own files.
+//! Supported: `[table]` / `[dotted.table]` headers, `key = value` pairs with strings, integers,
+//! booleans and single-line arrays of those, and `#` comments.
+use std::{collections::BTreeMap, fmt::Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(s) => {
+                f.write_str("\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                f.write_str("\"")
+            }
+            Value::Integer(i) => write!(f, "{i}"),
+            Value::Boolean(b) => write!(f, "{b}"),
+            Value::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+/// A key-value table. Keys keep their insertion order when written back.
+pub type Table = Vec<(String, Value)>;
+
+/// A parsed document. The root table is stored under the empty name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Document {
+    tables: BTreeMap<String, Table>,
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::error::Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the table with the given dotted name, e.g. `days.05`.
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(name)
+    }
+
+    /// Iterates all tables whose name starts with `prefix.`, yielding the remainder of the name.
+    pub fn subtables<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'a str, &'a Table)> {
+        self.tables.iter().filter_map(move |(name, table)| {
+            name.strip_prefix(prefix)?
+                .strip_prefix('.')
+                .map(|rest| (rest, table))
+        })
+    }
+
+    pub fn get(&self, table: &str, key: &str) -> Option<&Value> {
+        self.table(table)?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn set(&mut self, table: &str, key: &str, value: Value) {
+        let entries = self.tables.entry(table.to_string()).or_default();
+        match entries.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((key.to_string(), value)),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut doc = Document::new();
+        let mut current = String::new();
+
+        for (i, raw) in s.lines().enumerate() {
+            let line_no = i + 1;
+            let err = |message: &str| ParseError {
+                line: line_no,
+                message: message.into(),
+            };
+
+            let line = strip_comment(raw).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| err("unterminated table header"))?
+                    .trim();
+                if name.is_empty() || !name.split('.').all(is_bare_key) {
+                    return Err(err("invalid table name"));
+                }
+                current = name.to_string();
+                doc.tables.entry(current.clone()).or_default();
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| err("expected `key = value`"))?;
+            let key = key.trim();
+
+            if !is_bare_key(key) {
+                return Err(err("invalid key"));
+            }
+
+            let (value, rest) = parse_value(value.trim()).map_err(|m| err(&m))?;
+
+            if !rest.trim().is_empty() {
+                return Err(err("unexpected trailing characters"));
+            }
+
+            doc.set(&current, key, value);
+        }
+
+        Ok(doc)
+    }
+}
+
+impl Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for (name, table) in &self.tables {
+            if !name.is_empty() {
+                if !first {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[{name}]")?;
+            }
+            for (key, value) in table {
+                writeln!(f, "{key} = {value}")?;
+            }
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Removes a trailing `#` comment, ignoring `#` characters inside strings.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Parses a single value from the start of `s`, returning it alongside the unparsed remainder.
+fn parse_value(s: &str) -> Result<(Value, &str), String> {
+    if let Some(rest) = s.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = rest.char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Ok((Value::String(out), &rest[i + 1..])),
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    _ => return Err("invalid escape sequence".into()),
+                },
+                c => out.push(c),
+            }
+        }
+
+        return Err("unterminated string".into());
+    }
+
+    if let Some(mut rest) = s.strip_prefix('[') {
+        let mut values = vec![];
+
+        loop {
+            rest = rest.trim_start();
+
+            if let Some(after) = rest.strip_prefix(']') {
+                return Ok((Value::Array(values), after));
+            }
+
+            let (value, after) = parse_value(rest)?;
+            values.push(value);
+            rest = after.trim_start();
+
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after;
+            } else if !rest.starts_with(']') {
+                return Err("expected `,` or `]` in array".into());
+            }
+        }
+    }
+
+    let end = s.find([',', ']', ' ']).unwrap_or(s.len());
+    let (token, rest) = s.split_at(end);
+
+    let value = match token {
+        "true" => Value::Boolean(true),
+        "false" => Value::Boolean(false),
+        t => Value::Integer(
+            t.replace('_', "")
+                .parse()
+                .map_err(|_| format!("unsupported value `{t}`"))?,
+        ),
+    };
+
+    Ok((value, rest))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Document, Value};
+
+    #[test]
+    fn parses_tables_and_values() {
+        let doc = Document::parse(
+            "year = 2023 # comment\n\n[paths]\ndata = \"my # data\"\n\n[days.05]\nmax_samples = 1_000\nskip = true\ncolumns = [\"a\", 2]\n",
+        )
+        .unwrap();
+
+        assert_eq!(doc.get("", "year"), Some(&Value::Integer(2023)));
+        assert_eq!(
+            doc.get("paths", "data").and_then(Value::as_str),
+            Some("my # data")
+        );
+        assert_eq!(
+            doc.get("days.05", "max_samples"),
+            Some(&Value::Integer(1000))
+        );
+        assert_eq!(doc.get("days.05", "skip"), Some(&Value::Boolean(true)));
+        assert_eq!(
+            doc.get("days.05", "columns"),
+            Some(&Value::Array(vec![
+                Value::String("a".into()),
+                Value::Integer(2)
+            ]))
+        );
+        assert_eq!(
+            doc.subtables("days")
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            ["05"]
+        );
+    }
+
+    #[test]
+    fn reports_line_of_error() {
+        let err = Document::parse("year = 2023\n[paths\n").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn roundtrips_escaped_strings() {
+        let mut doc = Document::new();
+        doc.set("part_1", "answer", Value::String("a \"b\"\n#c".into()));
+        let parsed = Document::parse(&doc.to_string()).unwrap();
+        assert_eq!(parsed, doc);
+    }
+}